@@ -6,18 +6,42 @@
 
 use std::{cell::Cell, marker::PhantomData};
 use std::rc::Rc;
+use std::path::PathBuf;
+use std::collections::HashMap;
 
 use glow::{HasContext, UniformLocation};
 use smallvec::SmallVec;
 
 #[derive(Debug, Clone)]
-pub struct GLError(u32);
+pub enum GLError {
+    /// An error code, as returned by `glGetError`.
+    Api(u32),
+    /// The OpenGL context has been lost, all its objects are gone.
+    ///
+    /// The renderer must be rebuilt with a new context.
+    ContextLost,
+}
+
+// glow does not define it
+const CONTEXT_LOST_WEBGL: u32 = 0x9242;
+
+impl GLError {
+    fn from_code(err: u32) -> GLError {
+        match err {
+            glow::CONTEXT_LOST | CONTEXT_LOST_WEBGL => GLError::ContextLost,
+            _ => GLError::Api(err),
+        }
+    }
+}
 
 impl std::error::Error for GLError {
 }
 impl std::fmt::Display for GLError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:x}", self.0)
+        match self {
+            GLError::Api(err) => write!(f, "{:x}", err),
+            GLError::ContextLost => write!(f, "context lost"),
+        }
     }
 }
 
@@ -26,17 +50,21 @@ pub type Result<T> = std::result::Result<T, GLError>;
 /// A shareable OpenGL context.
 pub type GlContext = Rc<glow::Context>;
 
+/// Checks for a pending OpenGL error.
+///
+/// A lost context is reported as `GLError::ContextLost`, so call it after rendering to know if
+/// the renderer has to be rebuilt.
 pub fn check_gl(gl: &GlContext) -> std::result::Result<(), GLError> {
     let err = unsafe { gl.get_error() };
     if err == glow::NO_ERROR {
         Ok(())
     } else {
-        Err(GLError(err))
+        Err(GLError::from_code(err))
     }
 }
 
 pub fn to_gl_err(gl: &GlContext) -> GLError {
-    unsafe { GLError(gl.get_error()) }
+    unsafe { GLError::from_code(gl.get_error()) }
 }
 
 pub struct Texture {
@@ -72,6 +100,26 @@ impl Texture {
         std::mem::forget(self);
         id
     }
+    /// Replaces the contents of this texture with an RGBA8 image, with linear filtering and no mipmaps.
+    ///
+    /// Panics if `pixels` is smaller than `width * height * 4`.
+    pub fn set_rgba_image(&self, width: i32, height: i32, pixels: &[u8]) {
+        assert!(pixels.len() >= (width * height * 4) as usize, "not enough pixels for the texture size");
+        unsafe {
+            self.gl.bind_texture(glow::TEXTURE_2D, Some(self.id));
+
+            self.gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE as i32);
+            self.gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE as i32);
+            self.gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::LINEAR as i32);
+            self.gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::LINEAR as i32);
+            self.gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAX_LEVEL, 0);
+            self.gl.tex_image_2d(glow::TEXTURE_2D, 0, glow::RGBA as i32,
+                           width, height, 0,
+                           glow::RGBA, glow::UNSIGNED_BYTE,
+                           Some(pixels));
+            self.gl.bind_texture(glow::TEXTURE_2D, None);
+        }
+    }
 }
 
 
@@ -141,6 +189,27 @@ pub struct Program {
     id: glow::Program,
     uniforms: Vec<Uniform>,
     attribs: Vec<Attribute>,
+    // name -> index in `uniforms`/`attribs`
+    uniforms_by_name: HashMap<String, usize>,
+    attribs_by_name: HashMap<String, usize>,
+    files: Option<ProgramFiles>,
+}
+
+// The source files of a program, to be able to reload it
+struct ProgramFiles {
+    vertex: PathBuf,
+    fragment: PathBuf,
+    geometry: Option<PathBuf>,
+}
+
+impl ProgramFiles {
+    fn build(&self, gl: &GlContext) -> anyhow::Result<Program> {
+        let vertex = std::fs::read_to_string(&self.vertex)?;
+        let fragment = std::fs::read_to_string(&self.fragment)?;
+        let geometry = self.geometry.as_ref().map(std::fs::read_to_string).transpose()?;
+        let prg = Program::from_source(gl, &vertex, &fragment, geometry.as_deref())?;
+        Ok(prg)
+    }
 }
 
 impl Drop for Program {
@@ -169,6 +238,9 @@ impl Program {
                 id,
                 uniforms: Vec::new(),
                 attribs: Vec::new(),
+                uniforms_by_name: HashMap::new(),
+                attribs_by_name: HashMap::new(),
+                files: None,
             };
             gl.attach_shader(prg.id, vsh.id);
             gl.attach_shader(prg.id, fsh.id);
@@ -181,7 +253,7 @@ impl Program {
             if !st {
                 let msg = gl.get_program_info_log(prg.id);
                 log::error!("{msg}");
-                return Err(GLError(gl.get_error()));
+                return Err(to_gl_err(gl));
             }
 
             let nu = gl.get_active_uniforms(prg.id);
@@ -212,18 +284,48 @@ impl Program {
                 };
                 prg.attribs.push(a);
             }
+            prg.uniforms_by_name = prg.uniforms.iter().enumerate().map(|(i, u)| (u.name.clone(), i)).collect();
+            prg.attribs_by_name = prg.attribs.iter().enumerate().map(|(i, a)| (a.name.clone(), i)).collect();
 
             Ok(prg)
         }
     }
+    /// Builds a program reading the shaders from source files.
+    ///
+    /// Later, the program can be rebuilt with [`Program::reload`], useful while developing the shaders.
+    pub fn from_files(gl: &GlContext, vertex: impl Into<PathBuf>, fragment: impl Into<PathBuf>, geometry: Option<PathBuf>) -> anyhow::Result<Program> {
+        let files = ProgramFiles {
+            vertex: vertex.into(),
+            fragment: fragment.into(),
+            geometry,
+        };
+        let mut prg = files.build(gl)?;
+        prg.files = Some(files);
+        Ok(prg)
+    }
+    /// Reads the source files again and rebuilds the program.
+    ///
+    /// If there is any error, the current program is kept unchanged.
+    /// It fails if the program was not created with [`Program::from_files`].
+    pub fn reload(&mut self) -> anyhow::Result<()> {
+        let Some(files) = &self.files else {
+            anyhow::bail!("program not created from files");
+        };
+        let mut prg = files.build(&self.gl)?;
+        prg.files = self.files.take();
+        *self = prg;
+        Ok(())
+    }
     pub fn id(&self) -> glow::Program {
         self.id
     }
     pub fn attrib_by_name(&self, name: &str) -> Option<&Attribute> {
-        self.attribs.iter().find(|a| a.name == name)
+        let idx = *self.attribs_by_name.get(name)?;
+        Some(&self.attribs[idx])
     }
     pub fn uniform_by_name(&self, name: &str) -> Option<&Uniform> {
-        self.uniforms.iter().find(|u| u.name == name)
+        let idx = *self.uniforms_by_name.get(name)?;
+        Some(&self.uniforms[idx])
     }
     pub fn draw<U, AS>(&self, uniforms: &U, attribs: AS, primitive: u32)
         where
@@ -247,6 +349,31 @@ impl Program {
             }
         }
     }
+    /// Draws `count` vertices without any vertex attribute.
+    ///
+    /// The shader must generate the geometry by itself, usually from `gl_VertexID`, such as
+    /// a fullscreen triangle with `count == 3`. Note that OpenGL core profiles still require a
+    /// vertex array object to be bound, even if it is empty.
+    pub fn draw_no_attribs<U>(&self, uniforms: &U, primitive: u32, count: usize)
+        where
+            U: UniformProvider,
+    {
+        if count == 0 {
+            return;
+        }
+        unsafe {
+            self.gl.use_program(Some(self.id));
+
+            for u in &self.uniforms {
+                uniforms.apply(&self.gl, u);
+            }
+
+            self.gl.draw_arrays(primitive, 0, count as i32);
+            if let Err(e) = check_gl(&self.gl) {
+                log::error!("Error {e:?}");
+            }
+        }
+    }
 }
 
 struct Shader {
@@ -279,7 +406,7 @@ impl Shader {
                 //TODO: get errors
                 let msg = gl.get_shader_info_log(sh.id);
                 log::error!("{msg}");
-                return Err(GLError(gl.get_error()));
+                return Err(to_gl_err(gl));
             }
             Ok(sh)
         }
@@ -458,6 +585,62 @@ unsafe impl<F: AttribField> AttribField for cgmath::Vector3<F> {
     }
 }
 
+/// A vertex type that can be built from a 2D position.
+///
+/// Used by the geometry helpers [`quad_vertices`], [`rect_vertices`] and [`grid_vertices`].
+/// For your own vertex types, fill the other fields with default values, or modify them later.
+pub trait FromPos2d {
+    fn from_pos_2d(pos: cgmath::Vector2<f32>) -> Self;
+}
+
+impl FromPos2d for cgmath::Vector2<f32> {
+    fn from_pos_2d(pos: cgmath::Vector2<f32>) -> Self {
+        pos
+    }
+}
+
+impl FromPos2d for [f32; 2] {
+    fn from_pos_2d(pos: cgmath::Vector2<f32>) -> Self {
+        pos.into()
+    }
+}
+
+/// Builds the two triangles of a quad with corners `p0`, `p1`, `p2` and `p3`, in that order.
+///
+/// It returns 6 vertices, to be drawn with `glow::TRIANGLES`: `p0, p1, p2` and `p0, p2, p3`.
+pub fn quad_vertices<A: FromPos2d>(p0: cgmath::Vector2<f32>, p1: cgmath::Vector2<f32>, p2: cgmath::Vector2<f32>, p3: cgmath::Vector2<f32>) -> Vec<A> {
+    [p0, p1, p2, p0, p2, p3]
+        .into_iter()
+        .map(A::from_pos_2d)
+        .collect()
+}
+
+/// Builds the two triangles of the axis-aligned rectangle `min`-`max`.
+pub fn rect_vertices<A: FromPos2d>(min: cgmath::Vector2<f32>, max: cgmath::Vector2<f32>) -> Vec<A> {
+    quad_vertices(
+        min,
+        cgmath::Vector2::new(max.x, min.y),
+        max,
+        cgmath::Vector2::new(min.x, max.y),
+    )
+}
+
+/// Builds the triangles of the rectangle `min`-`max` divided into `nx` by `ny` cells.
+///
+/// Each cell is a quad as returned by [`quad_vertices`], so it returns `6 * nx * ny` vertices,
+/// row by row.
+pub fn grid_vertices<A: FromPos2d>(min: cgmath::Vector2<f32>, max: cgmath::Vector2<f32>, nx: usize, ny: usize) -> Vec<A> {
+    let cell = cgmath::Vector2::new((max.x - min.x) / nx as f32, (max.y - min.y) / ny as f32);
+    let mut res = Vec::with_capacity(6 * nx * ny);
+    for y in 0..ny {
+        for x in 0..nx {
+            let p = cgmath::Vector2::new(min.x + cell.x * x as f32, min.y + cell.y * y as f32);
+            res.extend(rect_vertices::<A>(p, p + cell));
+        }
+    }
+    res
+}
+
 /// # Safety
 ///
 /// This trait returns pointers and size information to OpenGL, if it is wrong it will read out of bounds
@@ -566,36 +749,76 @@ impl<A0: AttribProviderList, A1: AttribProviderList> AttribProviderList for (A0,
 }
 
 pub struct DynamicVertexArray<A> {
-    data: Vec<A>,
+    data: DirtyData<A>,
     buf: Buffer,
     buf_len: Cell<usize>,
+}
+
+// The vertices of a `DynamicVertexArray`, with a flag for when they have to be uploaded again.
+struct DirtyData<A> {
+    data: Vec<A>,
     dirty: Cell<bool>,
 }
 
+impl<A> DirtyData<A> {
+    fn new(data: Vec<A>) -> Self {
+        DirtyData {
+            data,
+            dirty: Cell::new(true),
+        }
+    }
+    fn set(&mut self, data: Vec<A>) {
+        self.dirty.set(true);
+        self.data = data;
+    }
+    fn get_mut(&mut self, index: usize) -> Option<&mut A> {
+        let r = self.data.get_mut(index)?;
+        self.dirty.set(true);
+        Some(r)
+    }
+    fn iter_mut(&mut self) -> std::slice::IterMut<'_, A> {
+        self.dirty.set(true);
+        self.data.iter_mut()
+    }
+    fn at_mut(&mut self, index: usize) -> &mut A {
+        self.dirty.set(true);
+        &mut self.data[index]
+    }
+}
+
 impl<A: AttribProvider> DynamicVertexArray<A> {
     pub fn new(gl: &GlContext) -> Result<Self> {
         Self::from_data(gl, Vec::new())
     }
     pub fn from_data(gl: &GlContext, data: Vec<A>) -> Result<Self> {
         Ok(DynamicVertexArray {
-            data,
+            data: DirtyData::new(data),
             buf: Buffer::generate(gl)?,
             buf_len: Cell::new(0),
-            dirty: Cell::new(true),
         })
     }
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.data.data.is_empty()
     }
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.data.data.len()
     }
     pub fn set(&mut self, data: impl Into<Vec<A>>) {
-        self.dirty.set(true);
-        self.data = data.into();
+        self.data.set(data.into());
     }
     pub fn data(&self) -> &[A] {
-        &self.data[..]
+        &self.data.data[..]
+    }
+    pub fn get(&self, index: usize) -> Option<&A> {
+        self.data.data.get(index)
+    }
+    /// Like `IndexMut`, but returns `None` if out of range. Only then the buffer is not marked as dirty.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut A> {
+        self.data.get_mut(index)
+    }
+    /// Iterates over all the vertices, mutably. The buffer is marked as dirty only once.
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, A> {
+        self.data.iter_mut()
     }
     pub fn sub(&self, range: std::ops::Range<usize>) -> DynamicVertexArraySub<'_, A> {
         DynamicVertexArraySub {
@@ -604,25 +827,26 @@ impl<A: AttribProvider> DynamicVertexArray<A> {
         }
     }
     pub fn bind_buffer(&self) {
-        if self.data.is_empty() {
+        let data = &self.data.data;
+        if data.is_empty() {
             return;
         }
         unsafe {
             self.buf.gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.buf.id()));
-            if self.dirty.get() {
-                if self.data.len() > self.buf_len.get() {
+            if self.data.dirty.get() {
+                if data.len() > self.buf_len.get() {
                     self.buf.gl.buffer_data_u8_slice(glow::ARRAY_BUFFER,
-                        as_u8_slice(&self.data),
+                        as_u8_slice(data),
                         glow::DYNAMIC_DRAW
                     );
-                    self.buf_len.set(self.data.len());
+                    self.buf_len.set(data.len());
                 } else {
                     self.buf.gl.buffer_sub_data_u8_slice(glow::ARRAY_BUFFER,
                         0,
-                        as_u8_slice(&self.data)
+                        as_u8_slice(data)
                     );
                 }
-                self.dirty.set(false);
+                self.data.dirty.set(false);
             }
         }
     }
@@ -632,14 +856,13 @@ impl<A: AttribProvider> std::ops::Index<usize> for DynamicVertexArray<A> {
     type Output = A;
 
     fn index(&self, index: usize) -> &A {
-        &self.data[index]
+        &self.data.data[index]
     }
 }
 
 impl<A: AttribProvider> std::ops::IndexMut<usize> for DynamicVertexArray<A> {
     fn index_mut(&mut self, index: usize) -> &mut A {
-        self.dirty.set(true);
-        &mut self.data[index]
+        self.data.at_mut(index)
     }
 }
 
@@ -647,7 +870,7 @@ impl<A: AttribProvider> AttribProviderList for &DynamicVertexArray<A> {
     type KeepType = SmallVec<[EnablerVertexAttribArray; 8]>;
 
     fn len(&self) -> usize {
-        self.data.len()
+        self.data.data.len()
     }
 
     fn bind(&self, p: &Program) -> SmallVec<[EnablerVertexAttribArray; 8]> {
@@ -836,6 +1059,28 @@ impl Framebuffer {
     pub fn id(&self) -> glow::Framebuffer {
         self.id
     }
+    /// Reads the RGBA8 pixels of a rectangle of this framebuffer, see [`read_pixels`].
+    pub fn read_pixels(&self, x: i32, y: i32, width: i32, height: i32) -> Vec<u8> {
+        let _bind = BinderReadFramebuffer::bind(self);
+        read_pixels(&self.gl, x, y, width, height)
+    }
+}
+
+/// Reads the RGBA8 pixels of a rectangle of the current read framebuffer.
+///
+/// As usual in OpenGL, the rows are returned bottom to top. This works with any context,
+/// including a headless one, such as an EGL pbuffer, so it can be used to render UI images
+/// without a window.
+pub fn read_pixels(gl: &GlContext, x: i32, y: i32, width: i32, height: i32) -> Vec<u8> {
+    let mut pixels = vec![0; (width.max(0) * height.max(0) * 4) as usize];
+    unsafe {
+        // The rows are tightly packed, but do not change the global state for later readbacks
+        let prev_alignment = gl.get_parameter_i32(glow::PACK_ALIGNMENT);
+        gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
+        gl.read_pixels(x, y, width, height, glow::RGBA, glow::UNSIGNED_BYTE, glow::PixelPackData::Slice(&mut pixels));
+        gl.pixel_store_i32(glow::PACK_ALIGNMENT, prev_alignment);
+    }
+    pixels
 }
 
 
@@ -914,3 +1159,78 @@ pub type BinderReadFramebuffer = BinderFramebuffer<BinderFBORead>;
 pub unsafe fn as_u8_slice<T>(data: &[T]) -> &[u8] {
     std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gl_error_from_code() {
+        assert!(matches!(GLError::from_code(glow::CONTEXT_LOST), GLError::ContextLost));
+        assert!(matches!(GLError::from_code(0x0507), GLError::ContextLost));
+        assert!(matches!(GLError::from_code(CONTEXT_LOST_WEBGL), GLError::ContextLost));
+        assert!(matches!(GLError::from_code(glow::INVALID_ENUM), GLError::Api(glow::INVALID_ENUM)));
+        assert!(matches!(GLError::from_code(glow::OUT_OF_MEMORY), GLError::Api(glow::OUT_OF_MEMORY)));
+    }
+
+    fn v(x: f32, y: f32) -> cgmath::Vector2<f32> {
+        cgmath::Vector2::new(x, y)
+    }
+
+    #[test]
+    fn quad_and_rect_vertices() {
+        let quad: Vec<[f32; 2]> = quad_vertices(v(0.0, 0.0), v(1.0, 0.0), v(1.0, 1.0), v(0.0, 1.0));
+        assert_eq!(quad, [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+        let rect: Vec<[f32; 2]> = rect_vertices(v(1.0, 2.0), v(3.0, 5.0));
+        assert_eq!(rect, [[1.0, 2.0], [3.0, 2.0], [3.0, 5.0], [1.0, 2.0], [3.0, 5.0], [1.0, 5.0]]);
+    }
+
+    #[test]
+    fn grid_vertices_count_and_corners() {
+        let grid: Vec<[f32; 2]> = grid_vertices(v(0.0, 0.0), v(4.0, 2.0), 2, 1);
+        assert_eq!(grid.len(), 6 * 2);
+        // Each cell is a rect, row by row
+        assert_eq!(grid[..6], rect_vertices::<[f32; 2]>(v(0.0, 0.0), v(2.0, 2.0))[..]);
+        assert_eq!(grid[6..], rect_vertices::<[f32; 2]>(v(2.0, 0.0), v(4.0, 2.0))[..]);
+
+        let grid: Vec<[f32; 2]> = grid_vertices(v(-1.0, -2.0), v(2.0, 2.0), 3, 4);
+        assert_eq!(grid.len(), 6 * 3 * 4);
+        // First corner of the first cell and opposite corner of the last cell
+        assert_eq!(grid[0], [-1.0, -2.0]);
+        assert_eq!(grid[grid.len() - 4], [2.0, 2.0]);
+    }
+
+    #[test]
+    fn dirty_data() {
+        let mut data = DirtyData::new(vec![1, 2, 3]);
+        assert!(data.dirty.get());
+        data.dirty.set(false);
+
+        assert!(data.get_mut(3).is_none());
+        assert!(!data.dirty.get());
+        *data.get_mut(1).unwrap() = 20;
+        assert!(data.dirty.get());
+        assert_eq!(data.data, [1, 20, 3]);
+
+        data.dirty.set(false);
+        data.iter_mut().for_each(|x| *x += 1);
+        assert!(data.dirty.get());
+        assert_eq!(data.data, [2, 21, 4]);
+
+        data.dirty.set(false);
+        *data.at_mut(0) = 0;
+        assert!(data.dirty.get());
+
+        data.dirty.set(false);
+        data.set(Vec::new());
+        assert!(data.dirty.get());
+    }
+
+    #[test]
+    fn grid_vertices_degenerate() {
+        for (nx, ny) in [(0, 0), (0, 3), (3, 0)] {
+            let grid: Vec<[f32; 2]> = grid_vertices(v(0.0, 0.0), v(1.0, 1.0), nx, ny);
+            assert!(grid.is_empty());
+        }
+    }
+}
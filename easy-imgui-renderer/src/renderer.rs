@@ -9,6 +9,10 @@ use imgui::{TextureId, Color, Vector2};
 use crate::glr;
 
 /// The main `Renderer` type.
+///
+/// It owns the Dear ImGui context and the GL objects needed to draw it. Each frame, call
+/// [`Renderer::set_size`] if the window changed, then [`Renderer::do_frame`]. The latter
+/// rebuilds the font atlas and its texture when needed, builds the UI and renders it.
 pub struct Renderer {
     imgui: imgui::Context,
     gl: glr::GlContext,
@@ -142,7 +146,7 @@ impl Renderer {
             let mut imgui = self.imgui.set_current();
 
             if imgui.update_atlas(app) {
-                Self::update_atlas(&self.gl, &self.objs.atlas);
+                Self::update_atlas(&self.objs.atlas);
             }
 
             imgui.do_frame(
@@ -165,33 +169,12 @@ impl Renderer {
             );
         }
     }
-    unsafe fn update_atlas(gl: &glr::GlContext, atlas_tex: &glr::Texture) {
-        let io = ImGui_GetIO();
-        let mut data = std::ptr::null_mut();
-        let mut width = 0;
-        let mut height = 0;
-        let mut pixel_size = 0;
-        ImFontAtlas_GetTexDataAsRGBA32((*io).Fonts, &mut data, &mut width, &mut height, &mut pixel_size);
-
-        gl.bind_texture(glow::TEXTURE_2D, Some(atlas_tex.id()));
-
-        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE as i32);
-        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE as i32);
-        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MIN_FILTER, glow::LINEAR as i32);
-        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAG_FILTER, glow::LINEAR as i32);
-        gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAX_LEVEL, 0);
-        gl.tex_image_2d(glow::TEXTURE_2D, 0, glow::RGBA as i32, //glow::RED as i32,
-                       width, height, 0,
-                       glow::RGBA, glow::UNSIGNED_BYTE,
-                       //glow::RED, glow::UNSIGNED_BYTE,
-                       Some(std::slice::from_raw_parts(data, (width * height * pixel_size) as usize)));
-        gl.bind_texture(glow::TEXTURE_2D, None);
+    unsafe fn update_atlas(atlas_tex: &glr::Texture) {
+        upload_font_atlas(atlas_tex);
 
         // bindgen: ImFontAtlas_SetTexID is inline
+        let io = ImGui_GetIO();
         (*(*io).Fonts).TexID = Self::map_tex(atlas_tex.id()).id();
-
-        // We keep this, no need for imgui to hold a copy
-        ImFontAtlas_ClearTexData((*io).Fonts);
     }
     unsafe fn render(gl: &glow::Context, objs: &GlObjects, draw_data: &ImDrawData) {
         gl.bind_vertex_array(Some(objs.vao.id()));
@@ -279,6 +262,8 @@ impl Renderer {
                         gl.bind_texture(glow::TEXTURE_2D, Self::unmap_tex(TextureId::from_id(cmd.TextureId)));
 
                         if cfg!(target_arch="wasm32") {
+                            // WebGL has no base vertex, that is why RendererHasVtxOffset is not advertised there
+                            debug_assert_eq!(cmd.VtxOffset, 0);
                             gl.draw_elements(
                                 glow::TRIANGLES,
                                 cmd.ElemCount as i32,
@@ -332,6 +317,41 @@ impl Renderer {
     }
 }
 
+impl glr::Texture {
+    /// Creates a new texture with the font atlas of the current Dear ImGui context.
+    ///
+    /// The atlas is built if needed, and its pixel data is released from the atlas afterwards.
+    /// Useful if you are not using [`Renderer`] but you still want to draw the UI.
+    ///
+    /// The texture is owned by the caller, the atlas `TexID` is not changed. Set it to
+    /// `Renderer::map_tex(tex.id())` and keep the texture alive while Dear ImGui draws with that
+    /// id: dropping it deletes the GL texture.
+    ///
+    /// # Safety
+    ///
+    /// There must be a current Dear ImGui context, and no frame in progress.
+    pub unsafe fn from_font_atlas(gl: &glr::GlContext) -> Result<glr::Texture> {
+        let tex = glr::Texture::generate(gl)?;
+        upload_font_atlas(&tex);
+        Ok(tex)
+    }
+}
+
+// Copies the font atlas of the current context into `tex`.
+unsafe fn upload_font_atlas(tex: &glr::Texture) {
+    let io = ImGui_GetIO();
+    let mut data = std::ptr::null_mut();
+    let mut width = 0;
+    let mut height = 0;
+    let mut pixel_size = 0;
+    ImFontAtlas_GetTexDataAsRGBA32((*io).Fonts, &mut data, &mut width, &mut height, &mut pixel_size);
+
+    tex.set_rgba_image(width, height, std::slice::from_raw_parts(data, (width * height * pixel_size) as usize));
+
+    // We keep this, no need for imgui to hold a copy
+    ImFontAtlas_ClearTexData((*io).Fonts);
+}
+
 #[cfg(target_arch="wasm32")]
 static WASM_TEX_MAP: std::sync::Mutex<Vec<glow::Texture>> = std::sync::Mutex::new(Vec::new());
 
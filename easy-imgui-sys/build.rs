@@ -58,6 +58,14 @@ fn main() {
 struct ImGuiContext;
 extern thread_local ImGuiContext* MyImGuiTLS;
 #define GImGui MyImGuiTLS
+
+// Failed assertions call a Rust function, see `easy_imgui_sys::set_assert_handler()`.
+// Without a handler it prints and aborts, like `assert()`, but also in release builds.
+// Third party code that defines its own IM_ASSERT before including imgui.h keeps it.
+#ifndef IM_ASSERT
+extern "C" void EasyImGui_AssertFailed(const char* expr, const char* file, int line);
+#define IM_ASSERT(_EXPR) ((_EXPR) ? (void)0 : EasyImGui_AssertFailed(#_EXPR, __FILE__, __LINE__))
+#endif
         ").unwrap();
 
     println!(
@@ -4,9 +4,62 @@
 #![allow(clippy::all)]
 
 use std::ops::{Index, Deref};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::ffi::{CStr, c_char, c_int};
 
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 
+// The assertion handler of each Dear ImGui context. A context is only used in one thread, because
+// the current context is thread local, so a thread local list is enough.
+thread_local! {
+    static ASSERT_HANDLERS: RefCell<Vec<(*mut ImGuiContext, Rc<dyn Fn(&str, &str, u32)>)>> = RefCell::new(Vec::new());
+}
+
+/// Sets the function to be called when an `IM_ASSERT` fails while `ctx` is the current context.
+///
+/// The arguments are the failed expression, the source file and the line.
+/// If there is no handler, the default, the assertion is printed to `stderr` and the process
+/// aborts, as a C `assert` would do. Set `None` to remove the handler, for example before
+/// destroying the context.
+///
+/// # Safety
+///
+/// When the handler returns, Dear ImGui goes on past the failed assertion. Many assertions check
+/// for misuse that Dear ImGui then recovers from, such as calling `End()` too many times, but
+/// others guard memory accesses, and going on past those is undefined behavior. Use it to log and
+/// debug, or call `std::process::abort()` from the handler. If the handler panics the process aborts.
+pub unsafe fn set_assert_handler(ctx: *mut ImGuiContext, handler: Option<Box<dyn Fn(&str, &str, u32)>>) {
+    ASSERT_HANDLERS.with(|hs| {
+        let mut hs = hs.borrow_mut();
+        hs.retain(|(c, _)| *c != ctx);
+        if let Some(handler) = handler {
+            hs.push((ctx, Rc::from(handler)));
+        }
+    });
+}
+
+#[no_mangle]
+unsafe extern "C" fn EasyImGui_AssertFailed(expr: *const c_char, file: *const c_char, line: c_int) {
+    let expr = CStr::from_ptr(expr).to_string_lossy();
+    let file = CStr::from_ptr(file).to_string_lossy();
+    let ctx = ImGui_GetCurrentContext();
+    // Cloned out of the list, so that the handler can change it
+    let handler = ASSERT_HANDLERS
+        .try_with(|hs| hs.borrow().iter().find(|(c, _)| *c == ctx).map(|(_, h)| Rc::clone(h)))
+        .ok()
+        .flatten();
+    if let Some(handler) = handler {
+        // A panic must not unwind into C++
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| handler(&expr, &file, line as u32)));
+        if res.is_ok() {
+            return;
+        }
+    }
+    eprintln!("Dear ImGui assertion failed: {expr}, at {file}:{line}");
+    std::process::abort();
+}
+
 impl<T> Index<usize> for ImVector<T> {
     type Output = T;
 
@@ -33,6 +33,7 @@ pub struct MainWindowWithRenderer {
     last_input_time: Instant,
     last_input_frame: u32,
     current_cursor: Option<CursorIcon>,
+    current_ime: Option<imgui::PlatformImeData>,
 }
 
 impl MainWindow {
@@ -177,6 +178,7 @@ impl MainWindowWithRenderer {
             last_input_time: now,
             last_input_frame: 0,
             current_cursor: Some(CursorIcon::Default),
+            current_ime: None,
         }
     }
     /// Sets the time after which the UI will stop rendering, if there is no user input.
@@ -273,6 +275,14 @@ impl MainWindowWithRenderer {
                             }
                             self.main_window.gl_context.make_current(&self.main_window.surface).unwrap();
                             self.renderer.do_frame(app);
+
+                            let ime = self.renderer.imgui().set_current().platform_ime_data();
+                            if ime.want_visible && self.current_ime != Some(ime) {
+                                let pos = LogicalPosition::new(ime.input_pos.x, ime.input_pos.y);
+                                let size = LogicalSize::new(1.0, ime.input_line_height);
+                                self.main_window.window.set_ime_cursor_area(pos, size);
+                            }
+                            self.current_ime = Some(ime);
                         }
                         self.main_window.window.pre_present_notify();
                         self.main_window.surface.swap_buffers(&self.main_window.gl_context).unwrap();
@@ -384,18 +394,26 @@ impl MainWindowWithRenderer {
                     } => {
                         self.ping_user_input();
                         let mut imgui = unsafe { self.renderer.imgui().set_current() };
-                        let io = imgui.io_mut();
                         let (h, v) = match delta {
                             winit::event::MouseScrollDelta::LineDelta(h, v) => (*h, *v),
                             winit::event::MouseScrollDelta::PixelDelta(d) => {
-                                let scale = io.DisplayFramebufferScale.x;
+                                let scale = imgui.io().DisplayFramebufferScale.x;
                                 let f_scale = unsafe { ImGui_GetFontSize() };
                                 let scale = scale * f_scale;
                                 (d.x as f32 / scale, d.y as f32 / scale)
                             }
                         };
-                        unsafe {
-                            ImGuiIO_AddMouseWheelEvent(io, h, v);
+                        imgui.add_mouse_wheel_event(h, v);
+                    }
+                    Touch(winit::event::Touch { phase, location, id, .. }) => {
+                        self.ping_user_input();
+                        let mut imgui = unsafe { self.renderer.imgui().set_current() };
+                        if *phase == winit::event::TouchPhase::Cancelled {
+                            imgui.add_touch_cancel_event(*id);
+                        } else {
+                            let position: LogicalPosition<f32> = self.main_window.to_logical_pos(*location);
+                            let down = matches!(phase, winit::event::TouchPhase::Started | winit::event::TouchPhase::Moved);
+                            imgui.add_touch_event(*id, imgui::vec2(position.x, position.y), down);
                         }
                     }
                     MouseInput { state, button, .. } => {
@@ -440,47 +458,20 @@ mod clipboard {
 }
 #[cfg(feature="clipboard")]
 mod clipboard {
-    use std::ffi::{CString, CStr, c_void, c_char};
+    use std::cell::RefCell;
+    use std::rc::Rc;
     use easy_imgui as imgui;
 
     pub fn maybe_setup_clipboard(imgui: &mut imgui::CurrentContext<'_>) {
         if let Ok(ctx) = arboard::Clipboard::new() {
-            let clip = MyClipboard {
-                ctx,
-                text: CString::default(),
-            };
-            let io = imgui.io_mut();
-            io.ClipboardUserData = Box::into_raw(Box::new(clip)) as *mut c_void;
-            io.SetClipboardTextFn = Some(set_clipboard_text);
-            io.GetClipboardTextFn = Some(get_clipboard_text);
+            let ctx = Rc::new(RefCell::new(ctx));
+            let ctx_set = Rc::clone(&ctx);
+            imgui.set_clipboard(
+                move || ctx.borrow_mut().get_text().ok(),
+                move |text| {
+                    let _ = ctx_set.borrow_mut().set_text(text);
+                },
+            );
         }
     }
-    unsafe extern "C" fn set_clipboard_text(user: *mut c_void, text: *const c_char) {
-        let clip = &mut *(user as *mut MyClipboard);
-        if text.is_null() {
-            let _ = clip.ctx.clear();
-        } else {
-            let cstr = CStr::from_ptr(text);
-            let str = String::from_utf8_lossy(cstr.to_bytes()).to_string();
-            let _ = clip.ctx.set_text(str);
-        }
-    }
-
-    // The returned pointer should be valid for a while...
-    unsafe extern "C" fn get_clipboard_text(user: *mut c_void) -> *const c_char {
-        let clip = &mut *(user as *mut MyClipboard);
-        let Ok(text) = clip.ctx.get_text() else {
-            return std::ptr::null();
-        };
-        let Ok(text) = CString::new(text) else {
-            return std::ptr::null();
-        };
-        clip.text = text;
-        clip.text.as_ptr()
-    }
-
-    struct MyClipboard {
-        ctx: arboard::Clipboard,
-        text: CString,
-    }
 }
@@ -32,6 +32,18 @@ macro_rules! imgui_enum_ex {
                 Some(r)
             }
         }
+        impl TryFrom<i32> for $name {
+            /// The unknown value.
+            type Error = i32;
+            fn try_from(bits: i32) -> Result<Self, i32> {
+                Self::from_bits(bits).ok_or(bits)
+            }
+        }
+        impl From<$name> for i32 {
+            fn from(x: $name) -> i32 {
+                x.bits()
+            }
+        }
     }
 }
 
@@ -269,6 +281,13 @@ imgui_flags!{
     }
 }
 
+impl ComboFlags {
+    /// Enables [`Ui::with_combo_preview`](crate::Ui::with_combo_preview).
+    ///
+    /// This is a flag from the internal Dear ImGui API.
+    pub const CustomPreview: ComboFlags = ComboFlags::from_bits_retain(ImGuiComboFlagsPrivate_::ImGuiComboFlags_CustomPreview.0 as i32);
+}
+
 imgui_flags!{
     pub SelectableFlags: ImGuiSelectableFlags_ {
         None,
@@ -366,6 +385,31 @@ impl MouseButton {
     }
 }
 
+impl TryFrom<i32> for MouseButton {
+    /// The unknown value.
+    type Error = i32;
+    fn try_from(bits: i32) -> Result<Self, i32> {
+        const Left: i32 = ImGuiMouseButton_::ImGuiMouseButton_Left.0 as i32;
+        const Right: i32 = ImGuiMouseButton_::ImGuiMouseButton_Right.0 as i32;
+        const Middle: i32 = ImGuiMouseButton_::ImGuiMouseButton_Middle.0 as i32;
+        const Count: i32 = ImGuiMouseButton_::ImGuiMouseButton_COUNT.0 as i32;
+        let r = match bits {
+            Left => MouseButton::Left,
+            Right => MouseButton::Right,
+            Middle => MouseButton::Middle,
+            x if (0..Count).contains(&x) => MouseButton::Other(x as u16),
+            _ => return Err(bits),
+        };
+        Ok(r)
+    }
+}
+
+impl From<MouseButton> for i32 {
+    fn from(x: MouseButton) -> i32 {
+        x.bits()
+    }
+}
+
 imgui_enum!{
     pub MouseCursor : ImGuiMouseCursor_ {
         None,
@@ -381,6 +425,22 @@ imgui_enum!{
     }
 }
 
+imgui_enum_ex!{
+    pub MouseSource: ImGuiMouseSource {
+        Mouse = ImGuiMouseSource_Mouse,
+        TouchScreen = ImGuiMouseSource_TouchScreen,
+        Pen = ImGuiMouseSource_Pen,
+    }
+}
+
+imgui_enum_ex!{
+    pub SortDirection: ImGuiSortDirection {
+        None = ImGuiSortDirection_None,
+        Ascending = ImGuiSortDirection_Ascending,
+        Descending = ImGuiSortDirection_Descending,
+    }
+}
+
 // ImGuiKey is named weirdly
 imgui_enum_ex!{
     pub Key: ImGuiKey {
@@ -696,6 +756,12 @@ imgui_flags! {
         HasMouseCursors,
         HasSetMousePos,
         RendererHasVtxOffset,
+        #[cfg(feature="docking")]
+        PlatformHasViewports,
+        #[cfg(feature="docking")]
+        HasMouseHoveredViewport,
+        #[cfg(feature="docking")]
+        RendererHasViewports,
     }
 }
 
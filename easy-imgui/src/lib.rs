@@ -133,6 +133,8 @@ use std::ptr::{null, null_mut};
 use std::mem::MaybeUninit;
 use std::cell::{Cell, RefCell};
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use cstr::cstr;
 use easy_imgui_sys::*;
 pub use cgmath;
@@ -145,6 +147,8 @@ pub type Vector2 = cgmath::Vector2<f32>;
 
 mod enums;
 pub mod style;
+#[cfg(test)]
+mod tests;
 
 pub use enums::*;
 pub use easy_imgui_sys::{self, ImGuiID};
@@ -238,7 +242,39 @@ impl Color {
             ImGui_ColorConvertFloat4ToU32(&(*self).into())
         }
     }
+    /// Builds a `Color` from HSV values, each value between 0.0 and 1.0.
+    pub fn from_hsv(h: f32, s: f32, v: f32, a: f32) -> Color {
+        let (r, g, b) = hsv_to_rgb(h, s, v);
+        Color::new(r, g, b, a)
+    }
+    /// Converts the RGB part of this `Color` into HSV values, each value between 0.0 and 1.0.
+    pub fn to_hsv(&self) -> (f32, f32, f32) {
+        rgb_to_hsv(self.r, self.g, self.b)
+    }
+}
+
+/// Converts HSV values into RGB, all of them between 0.0 and 1.0.
+///
+/// This is the same conversion used by the Dear ImGui color pickers.
+pub fn hsv_to_rgb(h: f32, s: f32, v: f32) -> (f32, f32, f32) {
+    let (mut r, mut g, mut b) = (0.0, 0.0, 0.0);
+    unsafe {
+        ImGui_ColorConvertHSVtoRGB(h, s, v, &mut r, &mut g, &mut b);
+    }
+    (r, g, b)
+}
+
+/// Converts RGB values into HSV, all of them between 0.0 and 1.0.
+///
+/// This is the same conversion used by the Dear ImGui color pickers.
+pub fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let (mut h, mut s, mut v) = (0.0, 0.0, 0.0);
+    unsafe {
+        ImGui_ColorConvertRGBtoHSV(r, g, b, &mut h, &mut s, &mut v);
+    }
+    (h, s, v)
 }
+
 impl AsRef<[f32; 4]> for Color {
     fn as_ref(&self) -> &[f32; 4] {
         // SAFETY: Self is repr(C) so layout compatible with an array
@@ -277,6 +313,38 @@ impl From<Color> for ImVec4 {
 pub struct Context {
     imgui: *mut ImGuiContext,
     pending_atlas: bool,
+    timings: HashMap<String, Duration>,
+    primary_touch: Option<u64>,
+    clipboard: Option<Box<ClipboardHandlers>>,
+    font_scale: f32,
+}
+
+struct ClipboardHandlers {
+    get: Box<dyn Fn() -> Option<String>>,
+    set: Box<dyn Fn(&str)>,
+    // The text returned to ImGui must live until the next call
+    text: CString,
+}
+
+unsafe extern "C" fn get_clipboard_text_trampoline(user: *mut c_void) -> *const c_char {
+    let clip = &mut *(user as *mut ClipboardHandlers);
+    let Some(text) = (clip.get)() else {
+        return null();
+    };
+    let Ok(text) = CString::new(text) else {
+        return null();
+    };
+    clip.text = text;
+    clip.text.as_ptr()
+}
+
+unsafe extern "C" fn set_clipboard_text_trampoline(user: *mut c_void, text: *const c_char) {
+    let clip = &*(user as *const ClipboardHandlers);
+    if text.is_null() {
+        return;
+    }
+    let text = CStr::from_ptr(text).to_string_lossy();
+    (clip.set)(&text);
 }
 
 pub struct CurrentContext<'a> {
@@ -305,6 +373,10 @@ impl Context {
         Context {
             imgui,
             pending_atlas: true,
+            timings: HashMap::new(),
+            primary_touch: None,
+            clipboard: None,
+            font_scale: 1.0,
         }
     }
     /// Makes this context the current one.
@@ -319,9 +391,34 @@ impl Context {
     }
     /// The next time [`CurrentContext::do_frame()`] is called, it will trigger a call to
     /// [`UiBuilder::build_custom_atlas`].
+    ///
+    /// All the fonts are cleared before that, so every `FontId` you have stored becomes invalid.
+    /// This is how to replace the whole font list, e.g. when switching themes.
     pub fn invalidate_font_atlas(&mut self) {
         self.pending_atlas = true;
     }
+    /// The time accumulated by every call to [`Ui::scoped_timing`], by label.
+    pub fn timings(&self) -> &HashMap<String, Duration> {
+        &self.timings
+    }
+    /// Resets all the timings accumulated by [`Ui::scoped_timing`].
+    pub fn clear_timings(&mut self) {
+        self.timings.clear();
+    }
+    /// Sets a function to be called when a Dear ImGui assertion fails in this context, such as
+    /// mismatched Begin/End calls.
+    ///
+    /// The argument is a description of the failed assertion. Without a callback, the default,
+    /// it is printed and the process aborts. When the callback returns, Dear ImGui goes on, so a
+    /// misuse that it knows how to recover from can be just logged.
+    ///
+    /// SAFETY: Some assertions guard memory accesses, and going on past those is undefined
+    /// behavior, see [`easy_imgui_sys::set_assert_handler`]. Use it for debugging.
+    pub unsafe fn set_error_callback(&mut self, cb: impl Fn(&str) + 'static) {
+        easy_imgui_sys::set_assert_handler(self.imgui, Some(Box::new(move |expr, file, line| {
+            cb(&format!("{expr}, at {file}:{line}"));
+        })));
+    }
 }
 
 impl CurrentContext<'_> {
@@ -331,6 +428,244 @@ impl CurrentContext<'_> {
             (*io).FontAllowUserScaling = val;
         }
     }
+    /// Time for a double-click, in seconds.
+    pub fn set_mouse_double_click_time(&mut self, secs: f32) {
+        unsafe {
+            let io = ImGui_GetIO();
+            (*io).MouseDoubleClickTime = secs;
+        }
+    }
+    pub fn mouse_double_click_time(&self) -> f32 {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            io.MouseDoubleClickTime
+        }
+    }
+    /// Distance threshold before considering we are dragging, in pixels.
+    ///
+    /// Touch screens usually need a bigger value than the default.
+    pub fn set_mouse_drag_threshold(&mut self, pixels: f32) {
+        unsafe {
+            let io = ImGui_GetIO();
+            (*io).MouseDragThreshold = pixels;
+        }
+    }
+    pub fn mouse_drag_threshold(&self) -> f32 {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            io.MouseDragThreshold
+        }
+    }
+    /// When holding a key, delay before it starts repeating, in seconds.
+    pub fn set_key_repeat_delay(&mut self, secs: f32) {
+        unsafe {
+            let io = ImGui_GetIO();
+            (*io).KeyRepeatDelay = secs;
+        }
+    }
+    pub fn key_repeat_delay(&self) -> f32 {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            io.KeyRepeatDelay
+        }
+    }
+    /// When holding a key, rate at which it repeats, in seconds.
+    pub fn set_key_repeat_rate(&mut self, secs: f32) {
+        unsafe {
+            let io = ImGui_GetIO();
+            (*io).KeyRepeatRate = secs;
+        }
+    }
+    pub fn key_repeat_rate(&self) -> f32 {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            io.KeyRepeatRate
+        }
+    }
+    /// Enable moving the windows only when clicking on their title bar.
+    pub fn set_config_windows_move_from_title_bar_only(&mut self, val: bool) {
+        unsafe {
+            let io = ImGui_GetIO();
+            (*io).ConfigWindowsMoveFromTitleBarOnly = val;
+        }
+    }
+    pub fn config_windows_move_from_title_bar_only(&self) -> bool {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            io.ConfigWindowsMoveFromTitleBarOnly
+        }
+    }
+    /// Enables the macOS conventions: Cmd instead of Ctrl for shortcuts, Alt to move by words, etc.
+    ///
+    /// It is enabled by default when Dear ImGui is built for Apple platforms.
+    pub fn set_config_mac_osx_behaviors(&mut self, val: bool) {
+        unsafe {
+            let io = ImGui_GetIO();
+            (*io).ConfigMacOSXBehaviors = val;
+        }
+    }
+    /// Queues a mouse wheel event.
+    ///
+    /// The values are in "lines" (1.0 is a normal mouse wheel step), but they can be fractional
+    /// for high-precision trackpads.
+    /// * `wheel_x`: horizontal scroll, positive means scrolling to the left.
+    /// * `wheel_y`: vertical scroll, positive means scrolling up.
+    pub fn add_mouse_wheel_event(&mut self, wheel_x: f32, wheel_y: f32) {
+        unsafe {
+            let io = ImGui_GetIO();
+            ImGuiIO_AddMouseWheelEvent(io, wheel_x, wheel_y);
+        }
+    }
+    /// Gets the IME data of the last frame.
+    ///
+    /// Backends can use it to place the IME candidate window next to the text cursor.
+    pub fn platform_ime_data(&self) -> PlatformImeData {
+        unsafe {
+            // Warning! internal imgui API ahead
+            let data = &(*self.ctx.imgui).PlatformImeData;
+            PlatformImeData {
+                want_visible: data.WantVisible,
+                input_pos: im_to_v2(data.InputPos),
+                input_line_height: data.InputLineHeight,
+            }
+        }
+    }
+    /// The monitors as reported by the platform backend, see [`CurrentContext::set_monitors`].
+    #[cfg(feature="docking")]
+    pub fn monitors(&self) -> Vec<MonitorInfo> {
+        unsafe {
+            let pio = &*ImGui_GetPlatformIO();
+            pio.Monitors.iter()
+                .map(|m| MonitorInfo {
+                    main_pos: im_to_v2(m.MainPos),
+                    main_size: im_to_v2(m.MainSize),
+                    work_pos: im_to_v2(m.WorkPos),
+                    work_size: im_to_v2(m.WorkSize),
+                    dpi_scale: m.DpiScale,
+                })
+                .collect()
+        }
+    }
+    /// Replaces the list of monitors in the platform IO.
+    ///
+    /// Backends should call this at startup and every time the monitor configuration changes.
+    /// Dear ImGui uses it to place and scale the viewports.
+    #[cfg(feature="docking")]
+    pub fn set_monitors(&mut self, monitors: &[MonitorInfo]) {
+        unsafe {
+            let pio = &mut *ImGui_GetPlatformIO();
+            let v = &mut pio.Monitors;
+            // The ImVector is owned by Dear ImGui, so use its allocator
+            if v.Capacity < monitors.len() as i32 {
+                let data = ImGui_MemAlloc(std::mem::size_of_val(monitors)) as *mut ImGuiPlatformMonitor;
+                ImGui_MemFree(v.Data as *mut c_void);
+                v.Data = data;
+                v.Capacity = monitors.len() as i32;
+            }
+            for (i, m) in monitors.iter().enumerate() {
+                let mut pm: ImGuiPlatformMonitor = std::mem::zeroed();
+                pm.MainPos = v2_to_im(m.main_pos);
+                pm.MainSize = v2_to_im(m.main_size);
+                pm.WorkPos = v2_to_im(m.work_pos);
+                pm.WorkSize = v2_to_im(m.work_size);
+                pm.DpiScale = m.dpi_scale;
+                v.Data.add(i).write(pm);
+            }
+            v.Size = monitors.len() as i32;
+        }
+    }
+    /// Copies the draw data of the last rendered frame, see [`DrawDataSnapshot`].
+    ///
+    /// Returns `None` if there is no valid draw data, for example before the first frame or
+    /// after [`CurrentContext::do_frame_without_render`].
+    pub fn snapshot_draw_data(&self) -> Option<DrawDataSnapshot> {
+        unsafe {
+            let dd = ImGui_GetDrawData();
+            if dd.is_null() || !(*dd).Valid {
+                return None;
+            }
+            Some(DrawDataSnapshot::new(&*dd))
+        }
+    }
+    /// Queues a change of the source of the mouse events.
+    ///
+    /// Dear ImGui uses it to adjust some behaviors, such as hovering, for touch screens.
+    pub fn add_mouse_source_event(&mut self, source: MouseSource) {
+        unsafe {
+            let io = ImGui_GetIO();
+            ImGuiIO_AddMouseSourceEvent(io, ImGuiMouseSource(source.bits()));
+        }
+    }
+    /// Queues a touch event.
+    ///
+    /// The first touch that goes down becomes the primary touch, and it is mapped to the mouse
+    /// position and the left button, until it is released. Other touches are ignored.
+    /// * `id`: an identifier of the touch, unique while the touch is down.
+    /// * `pos`: the position of the touch, in logical pixels.
+    /// * `down`: `true` while the touch is pressed, `false` when it is released. For a cancelled
+    ///   touch use [`CurrentContext::add_touch_cancel_event`] instead.
+    ///
+    /// Returns `true` if this touch was mapped to the mouse, or `false` if it is a secondary touch,
+    /// that the caller may use for custom gestures.
+    pub fn add_touch_event(&mut self, id: u64, pos: Vector2, down: bool) -> bool {
+        let pressed = match self.ctx.primary_touch {
+            None if down => {
+                self.ctx.primary_touch = Some(id);
+                true
+            }
+            Some(primary) if primary == id => false,
+            _ => return false,
+        };
+        unsafe {
+            let io = ImGui_GetIO();
+            ImGuiIO_AddMouseSourceEvent(io, ImGuiMouseSource::ImGuiMouseSource_TouchScreen);
+            // The position goes first, so that the item under the touch is hovered before it is pressed
+            ImGuiIO_AddMousePosEvent(io, pos.x, pos.y);
+            if pressed {
+                ImGuiIO_AddMouseButtonEvent(io, MouseButton::Left.bits(), true);
+            } else if !down {
+                ImGuiIO_AddMouseButtonEvent(io, MouseButton::Left.bits(), false);
+                self.ctx.primary_touch = None;
+            }
+        }
+        true
+    }
+    /// Cancels a touch, for example when the system takes it over for a gesture.
+    ///
+    /// Unlike releasing it with [`CurrentContext::add_touch_event`], the mouse is first moved
+    /// away, so the item under the touch is not clicked.
+    pub fn add_touch_cancel_event(&mut self, id: u64) {
+        if self.ctx.primary_touch != Some(id) {
+            return;
+        }
+        self.ctx.primary_touch = None;
+        unsafe {
+            let io = ImGui_GetIO();
+            ImGuiIO_AddMouseSourceEvent(io, ImGuiMouseSource::ImGuiMouseSource_TouchScreen);
+            // ImGui uses -FLT_MAX for an unavailable mouse
+            ImGuiIO_AddMousePosEvent(io, -f32::MAX, -f32::MAX);
+            ImGuiIO_AddMouseButtonEvent(io, MouseButton::Left.bits(), false);
+        }
+    }
+    /// Sets the functions to get and set the system clipboard text.
+    ///
+    /// The functions are kept alive as long as the [`Context`], or until this is called again.
+    /// `get` may return `None` if there is no text in the clipboard.
+    pub fn set_clipboard(&mut self, get: impl Fn() -> Option<String> + 'static, set: impl Fn(&str) + 'static) {
+        let mut clip = Box::new(ClipboardHandlers {
+            get: Box::new(get),
+            set: Box::new(set),
+            text: CString::default(),
+        });
+        unsafe {
+            let io = ImGui_GetIO();
+            // The Box contents do not move, so this pointer is stable
+            (*io).ClipboardUserData = &mut *clip as *mut ClipboardHandlers as *mut c_void;
+            (*io).GetClipboardTextFn = Some(get_clipboard_text_trampoline);
+            (*io).SetClipboardTextFn = Some(set_clipboard_text_trampoline);
+        }
+        self.ctx.clipboard = Some(clip);
+    }
     pub fn want_capture_mouse(&self) -> bool {
         unsafe {
             let io = &*ImGui_GetIO();
@@ -349,6 +684,43 @@ impl CurrentContext<'_> {
             io.WantTextInput
         }
     }
+    /// Time elapsed since the previous frame, in seconds.
+    pub fn delta_time(&self) -> f32 {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            io.DeltaTime
+        }
+    }
+    /// Sets the time elapsed since the previous frame, in seconds. It must be greater than 0.
+    pub fn set_delta_time(&mut self, secs: f32) {
+        unsafe {
+            let io = ImGui_GetIO();
+            (*io).DeltaTime = secs;
+        }
+    }
+    /// Estimation of the frames per second, averaged over the last frames.
+    pub fn framerate(&self) -> f32 {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            io.Framerate
+        }
+    }
+    pub fn config_flags(&self) -> ConfigFlags {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            ConfigFlags::from_bits_truncate(io.ConfigFlags)
+        }
+    }
+    /// Mouse position, in logical pixels, or `None` if the mouse is not available.
+    pub fn mouse_pos(&self) -> Option<Vector2> {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            // ImGui uses -FLT_MAX for an unavailable mouse
+            let pos = io.MousePos;
+            (pos.x > -f32::MAX && pos.y > -f32::MAX).then(|| im_to_v2(pos))
+        }
+    }
+    /// Raw access to the `ImGuiIO`, prefer the typed accessors when available.
     pub fn io(&self) -> &ImGuiIO {
         unsafe {
             &*ImGui_GetIO()
@@ -369,6 +741,22 @@ impl CurrentContext<'_> {
         let io = ImGui_GetIO();
         (*io).ConfigFlags &= !flags.bits();
     }
+    pub fn backend_flags(&self) -> BackendFlags {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            BackendFlags::from_bits_truncate(io.BackendFlags)
+        }
+    }
+    // Unsafe because advertising a capability the backend does not have will break things,
+    // for example `RendererHasVtxOffset` without honoring `ImDrawCmd::VtxOffset`.
+    pub unsafe fn add_backend_flags(&mut self, flags: BackendFlags) {
+        let io = ImGui_GetIO();
+        (*io).BackendFlags |= flags.bits();
+    }
+    pub unsafe fn remove_backend_flags(&mut self, flags: BackendFlags) {
+        let io = ImGui_GetIO();
+        (*io).BackendFlags &= !flags.bits();
+    }
     pub fn nav_enable_keyboard(&mut self) {
         unsafe {
             self.add_config_flags(ConfigFlags::NavEnableKeyboard);
@@ -379,15 +767,74 @@ impl CurrentContext<'_> {
             self.add_config_flags(ConfigFlags::NavEnableGamepad);
         }
     }
-    pub unsafe fn set_size(&mut self, size: Vector2, scale: f32) {
-        let io = ImGui_GetIO();
-        (*io).DisplaySize = v2_to_im(size);
-        if self.scale() != scale {
-            (*io).DisplayFramebufferScale = ImVec2 { x: scale, y: scale };
-            (*io).FontGlobalScale = scale.recip();
+    /// Sets the display size, in logical pixels, and the scale factor.
+    ///
+    /// The font atlas is invalidated only if `scale` is different from the current one, for
+    /// example when the window is moved to a monitor with a different DPI, so calling this every
+    /// frame does not rebuild the fonts. Then the next call to [`CurrentContext::update_atlas`]
+    /// bakes the fonts at the new scale.
+    ///
+    /// Since the fonts are baked at `scale`, the `FontGlobalScale` is set to undo it, see
+    /// [`CurrentContext::set_font_global_scale`].
+    ///
+    /// Panics if the size or the scale are not valid, see [`CurrentContext::set_display_size`]
+    /// and [`CurrentContext::set_framebuffer_scale`].
+    pub fn set_size(&mut self, size: Vector2, scale: f32) {
+        self.set_display_size(size.x, size.y);
+        self.set_framebuffer_scale(scale, scale);
+    }
+    /// Sets only the display size, in logical pixels.
+    ///
+    /// This never touches the font atlas, so it is the one to call when the window is resized
+    /// but the DPI did not change. A size of zero is fine, that is what a minimized window has.
+    ///
+    /// Panics if `width` or `height` are negative or not finite.
+    pub fn set_display_size(&mut self, width: f32, height: f32) {
+        assert!(width.is_finite() && width >= 0.0 && height.is_finite() && height >= 0.0,
+            "invalid display size {width}x{height}");
+        unsafe {
+            let io = ImGui_GetIO();
+            (*io).DisplaySize = ImVec2 { x: width, y: height };
+        }
+    }
+    /// Sets only the framebuffer scale, in physical pixels per logical pixel.
+    ///
+    /// If it is different from the current one the font atlas is invalidated, as in
+    /// [`CurrentContext::set_size`]. The fonts are baked using the `x` scale.
+    ///
+    /// Panics if `x` or `y` are not positive and finite.
+    pub fn set_framebuffer_scale(&mut self, x: f32, y: f32) {
+        assert!(x.is_finite() && x > 0.0 && y.is_finite() && y > 0.0,
+            "invalid framebuffer scale {x}x{y}");
+        let changed = unsafe {
+            let io = ImGui_GetIO();
+            let current = (*io).DisplayFramebufferScale;
+            let changed = current.x != x || current.y != y;
+            if changed {
+                (*io).DisplayFramebufferScale = ImVec2 { x, y };
+                (*io).FontGlobalScale = self.ctx.font_scale / x;
+            }
+            changed
+        };
+        if changed {
             self.invalidate_font_atlas();
         }
     }
+    /// Sets a zoom factor for all the text, 1.0 by default.
+    ///
+    /// This is independent of the display scale given to [`CurrentContext::set_size`]: the
+    /// fonts are still baked at the display scale and stretched by this factor, so big values
+    /// will look blurry. For a crisp zoom, change the font sizes and rebuild the atlas instead.
+    pub fn set_font_global_scale(&mut self, scale: f32) {
+        self.ctx.font_scale = scale;
+        unsafe {
+            let io = ImGui_GetIO();
+            (*io).FontGlobalScale = scale / (*io).DisplayFramebufferScale.x;
+        }
+    }
+    pub fn font_global_scale(&self) -> f32 {
+        self.ctx.font_scale
+    }
     pub unsafe fn size(&self) -> Vector2 {
         let io = ImGui_GetIO();
         im_to_v2((*io).DisplaySize)
@@ -434,12 +881,7 @@ impl CurrentContext<'_> {
         render: impl FnOnce(&ImDrawData),
     )
     {
-        let mut ui = Ui {
-            data: std::ptr::null_mut(),
-            generation: ImGui_GetFrameCount() as usize,
-            callbacks: RefCell::new(Vec::new()),
-            pending_atlas: Cell::new(false),
-        };
+        let mut ui = Ui::new();
 
         let io = ImGui_GetIO();
         (*io).BackendLanguageUserData = &ui as *const Ui<A> as *mut c_void;
@@ -462,7 +904,35 @@ impl CurrentContext<'_> {
         let draw_data = ImGui_GetDrawData();
         render(&*draw_data);
 
-        _guard.0.pending_atlas |= ui.pending_atlas.get();
+        _guard.0.merge_ui_state(&ui);
+    }
+    /// Builds a UI frame, but does not render it.
+    ///
+    /// The frame is finished with `EndFrame` instead of `Render`, so no draw data is generated and
+    /// `A::pre_render` is not called. It is useful to compute the layout, for example to get the
+    /// size of an auto-resizing window, without paying for the rendering.
+    pub unsafe fn do_frame_without_render<A: UiBuilder>(&mut self, app: &mut A) {
+        let ui = Ui::new();
+
+        let io = ImGui_GetIO();
+        (*io).BackendLanguageUserData = &ui as *const Ui<A> as *mut c_void;
+        let _guard = UiPtrToNullGuard(self.ctx);
+        ImGui_NewFrame();
+
+        app.do_ui(&ui);
+
+        ImGui_EndFrame();
+
+        _guard.0.merge_ui_state(&ui);
+    }
+}
+
+impl Context {
+    fn merge_ui_state<A>(&mut self, ui: &Ui<A>) {
+        self.pending_atlas |= ui.pending_atlas.get();
+        for (label, time) in ui.timings.take() {
+            *self.timings.entry(label).or_default() += time;
+        }
     }
 }
 
@@ -470,15 +940,128 @@ impl Drop for Context {
     fn drop(&mut self) {
         unsafe {
             ImGui_DestroyContext(self.imgui);
+            easy_imgui_sys::set_assert_handler(self.imgui, None);
         }
     }
 }
 
+/// The position of the text input cursor, for the IME.
+///
+/// See [`CurrentContext::platform_ime_data`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PlatformImeData {
+    /// A widget wants the IME to be visible.
+    pub want_visible: bool,
+    /// Position of the input cursor, in logical pixels.
+    pub input_pos: Vector2,
+    /// Line height of the text input.
+    pub input_line_height: f32,
+}
+
+/// The geometry of a monitor, in logical pixels.
+///
+/// See [`CurrentContext::set_monitors`].
+#[cfg(feature="docking")]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MonitorInfo {
+    pub main_pos: Vector2,
+    pub main_size: Vector2,
+    /// The usable area, without task bars and similar.
+    pub work_pos: Vector2,
+    pub work_size: Vector2,
+    pub dpi_scale: f32,
+}
+
+/// An owned copy of an `ImDrawData`.
+///
+/// The draw data is only valid until the next frame starts. This copy can be kept around, for
+/// example to compare the geometry of a frame in tests, without a GPU.
+#[derive(Debug, Clone)]
+pub struct DrawDataSnapshot {
+    pub display_pos: Vector2,
+    pub display_size: Vector2,
+    pub framebuffer_scale: Vector2,
+    pub lists: Vec<DrawListSnapshot>,
+}
+
+/// An owned copy of an `ImDrawList`, see [`DrawDataSnapshot`].
+#[derive(Debug, Clone)]
+pub struct DrawListSnapshot {
+    pub vertices: Vec<ImDrawVert>,
+    pub indices: Vec<ImDrawIdx>,
+    pub commands: Vec<DrawCmdSnapshot>,
+}
+
+/// An owned copy of an `ImDrawCmd`, see [`DrawDataSnapshot`].
+#[derive(Debug, Clone)]
+pub struct DrawCmdSnapshot {
+    /// Clipping rectangle, as `(min, max)`.
+    pub clip_rect: (Vector2, Vector2),
+    pub texture_id: TextureId,
+    pub vtx_offset: u32,
+    pub idx_offset: u32,
+    pub elem_count: u32,
+    /// The command is a user callback, not actual geometry. The callback itself is not copied.
+    pub is_callback: bool,
+}
+
+impl DrawDataSnapshot {
+    /// Copies the given draw data.
+    ///
+    /// Usually you will want [`CurrentContext::snapshot_draw_data`] instead.
+    ///
+    /// SAFETY: `dd` must be a valid draw data from Dear ImGui, such as the one passed to the
+    /// `render` function of [`CurrentContext::do_frame`], because its raw pointers are followed.
+    pub unsafe fn new(dd: &ImDrawData) -> DrawDataSnapshot {
+        let lists = dd.CmdLists.iter()
+            .map(|&list| {
+                let list = &*list;
+                let commands = list.CmdBuffer.iter()
+                    .map(|cmd| DrawCmdSnapshot {
+                        clip_rect: (vec2(cmd.ClipRect.x, cmd.ClipRect.y), vec2(cmd.ClipRect.z, cmd.ClipRect.w)),
+                        texture_id: TextureId::from_id(cmd.TextureId),
+                        vtx_offset: cmd.VtxOffset,
+                        idx_offset: cmd.IdxOffset,
+                        elem_count: cmd.ElemCount,
+                        is_callback: cmd.UserCallback.is_some(),
+                    })
+                    .collect();
+                DrawListSnapshot {
+                    vertices: list.VtxBuffer.to_vec(),
+                    indices: list.IdxBuffer.to_vec(),
+                    commands,
+                }
+            })
+            .collect();
+        DrawDataSnapshot {
+            display_pos: im_to_v2(dd.DisplayPos),
+            display_size: im_to_v2(dd.DisplaySize),
+            framebuffer_scale: im_to_v2(dd.FramebufferScale),
+            lists,
+        }
+    }
+    /// The total number of vertices of all the lists.
+    pub fn total_vtx_count(&self) -> usize {
+        self.lists.iter().map(|l| l.vertices.len()).sum()
+    }
+    /// The total number of indices of all the lists.
+    pub fn total_idx_count(&self) -> usize {
+        self.lists.iter().map(|l| l.indices.len()).sum()
+    }
+}
+
 struct UiPtrToNullGuard<'a>(&'a mut Context);
 
 impl Drop for UiPtrToNullGuard<'_> {
     fn drop(&mut self) {
         unsafe {
+            // If the UI code panicked, the frame may still be open, with unbalanced Begin/End calls.
+            // Close everything so that the next frame can start cleanly.
+            if std::thread::panicking() && (*self.0.imgui).WithinFrameScope {
+                // Warning! internal imgui API ahead
+                ImGui_ErrorCheckEndFrameRecover(None, null_mut());
+                ImGui_EndFrame();
+            }
             let io = ImGui_GetIO();
             (*io).BackendLanguageUserData = null_mut();
         }
@@ -490,6 +1073,10 @@ pub trait UiBuilder {
     /// This function is run the first time an ImGui context is used to create the font atlas.
     ///
     /// You can force new call by invalidating the current atlas by calling [`Context::invalidate_font_atlas`].
+    ///
+    /// The atlas is always empty when this is called, so the fonts from any previous call are
+    /// dropped and the new `FontId`s start from 0 again. If no font is added, Dear ImGui will use its
+    /// default font.
     fn build_custom_atlas(&mut self, _atlas: &mut FontAtlasMut<'_, Self>) {}
     /// This function is run after `do_ui` but before rendering.
     ///
@@ -511,23 +1098,63 @@ pub struct FontInfo {
     ttf: TtfData,
     size: f32,
     char_ranges: Vec<[ImWchar; 2]>,
+    name: Option<String>,
+    snap_size: bool,
+}
+
+/// The error returned by [`FontInfo::try_new`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FontInfoError {
+    /// The TTF data is empty.
+    EmptyTtf,
+    /// The font size is not a finite positive number.
+    InvalidSize(f32),
+}
+
+impl std::error::Error for FontInfoError {
+}
+impl std::fmt::Display for FontInfoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontInfoError::EmptyTtf => write!(f, "empty TTF data"),
+            FontInfoError::InvalidSize(size) => write!(f, "invalid font size {size}"),
+        }
+    }
 }
 
 impl FontInfo {
     /// Creates a new `FontInfo` from a TTF content and a font size.
+    ///
+    /// Nothing is checked here: an empty `ttf` or a size that is not positive will fail later,
+    /// when building the atlas. Use [`FontInfo::try_new`] to check them.
     pub fn new(ttf: impl Into<Cow<'static, [u8]>>, size: f32) -> FontInfo {
-        FontInfo {
-            ttf: TtfData::Bytes(ttf.into()),
-            size,
-            char_ranges: Vec::new(),
+        Self::with_ttf(TtfData::Bytes(ttf.into()), size)
+    }
+    /// Creates a new `FontInfo` from a TTF content and a font size, checking both.
+    ///
+    /// Dear ImGui would fail later, when building the atlas, with an empty `ttf` or a size that
+    /// is not positive.
+    pub fn try_new(ttf: impl Into<Cow<'static, [u8]>>, size: f32) -> Result<FontInfo, FontInfoError> {
+        let ttf = ttf.into();
+        if ttf.is_empty() {
+            return Err(FontInfoError::EmptyTtf);
+        }
+        if !(size.is_finite() && size > 0.0) {
+            return Err(FontInfoError::InvalidSize(size));
         }
+        Ok(Self::with_ttf(TtfData::Bytes(ttf), size))
     }
     /// Creates a `FontInfo` using the embedded default Dear ImGui font, with the given font size.
     pub fn default_font(size: f32) -> FontInfo {
+        Self::with_ttf(TtfData::DefaultFont, size)
+    }
+    fn with_ttf(ttf: TtfData, size: f32) -> FontInfo {
         FontInfo {
-            ttf: TtfData::DefaultFont,
+            ttf,
             size,
             char_ranges: Vec::new(),
+            name: None,
+            snap_size: true,
         }
     }
     /// Adds the given char range to this font info.
@@ -539,6 +1166,21 @@ impl FontInfo {
         self.char_ranges.push([ImWchar::from(*range.start()), ImWchar::from(*range.end())]);
         self
     }
+    /// Rounds the font size, once multiplied by the display scale, to whole pixels. It is on by default.
+    ///
+    /// Fractional pixel sizes make for blurry text, but with this option the actual size of the
+    /// text will not be exactly proportional to the DPI scale.
+    pub fn snap_size_to_pixels(mut self, snap: bool) -> Self {
+        self.snap_size = snap;
+        self
+    }
+    /// Sets a debug name for this font.
+    ///
+    /// It is shown in the Dear ImGui metrics and style editor windows. It will be truncated to 39 bytes.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
 }
 
 /// Represents any type that can be converted into something that can be deref'ed to a `&CStr`.
@@ -634,6 +1276,7 @@ pub struct Ui<A>
     generation: usize,
     callbacks: RefCell<Vec<UiCallback<A>>>,
     pending_atlas: Cell<bool>,
+    timings: RefCell<Vec<(String, Duration)>>,
 }
 
 /// Callbacks called during `A::do_ui()` will have the first argument as null, because the app value
@@ -729,7 +1372,7 @@ macro_rules! decl_builder_setter_vector2 {
 
 macro_rules! decl_builder_with_maybe_opt {
     ( $always_run_end:literal
-      $sname:ident, $func_beg:ident, $func_end:ident ($($life:lifetime),*) ( $( $gen_n:ident : $gen_d:tt ),* )
+      $sname:ident, $func_beg:ident, $func_end:ident $([ $($end_arg:ident),* ])? ($($life:lifetime),*) ( $( $gen_n:ident : $gen_d:tt ),* )
         (
             $(
                 $arg:ident ($($ty:tt)*) ($pass:expr),
@@ -776,7 +1419,7 @@ macro_rules! decl_builder_with_maybe_opt {
                 let r = f(bres);
                 unsafe {
                     if $always_run_end || bres {
-                        $func_end();
+                        $func_end($($($end_arg,)*)?);
                     }
                 }
                 r
@@ -824,18 +1467,59 @@ decl_builder_with!{Child, ImGui_BeginChild, ImGui_EndChild () (S: IntoCStr)
                 push: (),
             }
         }
+        /// Calls `f` inside a child that fills the available space, but leaving `reserve_bottom`
+        /// pixels below it, usually for a footer.
+        ///
+        /// `f` is only called if the child is visible.
+        pub fn with_child_fill<S: IntoCStr, R>(&self, name: S, reserve_bottom: f32, f: impl FnOnce() -> R) -> Option<R> {
+            // A negative size means "available space minus that"
+            self.child_config(name)
+                .size(vec2(0.0, -reserve_bottom.max(0.0)))
+                .with(f)
+        }
     }
 }
 
-decl_builder_with!{Window, ImGui_Begin, ImGui_End ('v) (S: IntoCStr)
+unsafe fn begin_window_helper(name: *const c_char, open: *mut bool, flags: WindowFlags, pos: Option<(ImVec2, Cond)>, size: Option<(ImVec2, Cond)>, bg_alpha: Option<f32>) -> bool {
+    if let Some((pos, cond)) = pos {
+        ImGui_SetNextWindowPos(&pos, cond.bits(), &im_vec2(0.0, 0.0));
+    }
+    if let Some((size, cond)) = size {
+        ImGui_SetNextWindowSize(&size, cond.bits());
+    }
+    if let Some(bg_alpha) = bg_alpha {
+        ImGui_SetNextWindowBgAlpha(bg_alpha);
+    }
+    ImGui_Begin(name, open, flags.bits())
+}
+
+decl_builder_with!{Window, begin_window_helper, ImGui_End ('v) (S: IntoCStr)
     (
         name (S::Temp) (name.as_ptr()),
         open (Option<&'v mut bool>) (optional_mut_bool(&mut open)),
-        flags (WindowFlags) (flags.bits()),
+        flags (WindowFlags) (flags),
+        pos (Option<(ImVec2, Cond)>) (pos),
+        size (Option<(ImVec2, Cond)>) (size),
+        bg_alpha (Option<f32>) (bg_alpha),
     )
     {
         decl_builder_setter!{open: &'v mut bool}
         decl_builder_setter!{flags: WindowFlags}
+        /// Same as calling `set_next_window_pos` before this window.
+        pub fn position(mut self, pos: Vector2, cond: Cond) -> Self {
+            self.pos = Some((v2_to_im(pos), cond));
+            self
+        }
+        /// Same as calling `set_next_window_size` before this window.
+        pub fn size(mut self, size: Vector2, cond: Cond) -> Self {
+            self.size = Some((v2_to_im(size), cond));
+            self
+        }
+        /// Same as calling `set_next_window_bg_alpha` before this window.
+        pub fn bg_alpha(mut self, bg_alpha: f32) -> Self {
+            self.bg_alpha = Some(bg_alpha);
+            self
+        }
     }
     {
         pub fn window_config<S: IntoCStr>(&self, name: S) -> Window<S> {
@@ -843,6 +1527,9 @@ decl_builder_with!{Window, ImGui_Begin, ImGui_End ('v) (S: IntoCStr)
                 name: name.into(),
                 open: None,
                 flags: WindowFlags::None,
+                pos: None,
+                size: None,
+                bg_alpha: None,
                 push: (),
             }
         }
@@ -870,6 +1557,14 @@ decl_builder!{ MenuItem -> bool, ImGui_MenuItem () (S1: IntoCStr, S2: IntoCStr)
         }
         decl_builder_setter!{selected: bool}
         decl_builder_setter!{enabled: bool}
+        /// Like `build()` but the checkmark shows the value of `selected`, and it is toggled
+        /// when the item is clicked. The `selected()` setter is ignored.
+        pub fn build_toggle(self, selected: &mut bool) -> bool {
+            let MenuItem { label, shortcut, selected: _, enabled } = self;
+            unsafe {
+                ImGui_MenuItem1(label.as_ptr(), optional_str(&shortcut), selected, enabled)
+            }
+        }
     }
     {
         pub fn menu_item_config<S: IntoCStr>(&self, label: S) -> MenuItem<S, &str> {
@@ -977,6 +1672,22 @@ decl_builder! { Checkbox -> bool, ImGui_Checkbox ('v) (S: IntoCStr)
         pub fn checkbox<S: IntoCStr>(&self, label: S, value: &mut bool) -> bool {
             self.checkbox_config(label, value).build()
         }
+        /// A checkbox that toggles the bits of `flags_value` in `flags`.
+        ///
+        /// It shows as mixed if only some of those bits are set.
+        pub fn checkbox_flags_i32<S: IntoCStr>(&self, label: S, flags: &mut i32, flags_value: i32) -> bool {
+            let label = label.into();
+            unsafe {
+                ImGui_CheckboxFlags(label.as_ptr(), flags, flags_value)
+            }
+        }
+        /// Same as [`Ui::checkbox_flags_i32`] but with unsigned flags.
+        pub fn checkbox_flags_u32<S: IntoCStr>(&self, label: S, flags: &mut u32, flags_value: u32) -> bool {
+            let label = label.into();
+            unsafe {
+                ImGui_CheckboxFlags1(label.as_ptr(), flags, flags_value)
+            }
+        }
     }
 }
 
@@ -1139,7 +1850,7 @@ decl_builder! { Selectable -> bool, ImGui_Selectable () (S: IntoCStr)
 }
 
 macro_rules! decl_builder_drag {
-    ($name:ident $func:ident $cfunc:ident $life:lifetime ($argty:ty) ($ty:ty) ($expr:expr)) => {
+    ($name:ident $func:ident $cfunc:ident $life:lifetime ($argty:ty) ($ty:ty) ($expr:expr) ($format:literal)) => {
         decl_builder! { $name -> bool, $cfunc ($life) (S: IntoCStr)
             (
                 label (S::Temp) (label.as_ptr()),
@@ -1152,12 +1863,20 @@ macro_rules! decl_builder_drag {
             )
             {
                 decl_builder_setter!{speed: f32}
+                /// Limits the value to `min..=max` while dragging.
+                ///
+                /// If `min == max`, as by default, the value is not bounded.
                 pub fn range(mut self, min: $argty, max: $argty) -> Self {
                     self.min = min;
                     self.max = max;
                     self
                 }
                 decl_builder_setter!{flags: SliderFlags}
+                /// Sets the `sprintf` format used to display the value.
+                pub fn format(mut self, format: impl Into<Cow<'static, CStr>>) -> Self {
+                    self.format = format.into();
+                    self
+                }
             }
             {
                 pub fn $func<$life, S: IntoCStr>(&self, label: S, value: $ty) -> $name<$life, S> {
@@ -1167,7 +1886,7 @@ macro_rules! decl_builder_drag {
                         speed: 1.0,
                         min: <$argty>::default(),
                         max: <$argty>::default(),
-                        format: Cow::Borrowed(cstr!("%.3f")),
+                        format: Cow::Borrowed(cstr!($format)),
                         flags: SliderFlags::None,
                     }
                 }
@@ -1193,23 +1912,79 @@ macro_rules! impl_float_format {
     };
 }
 
-decl_builder_drag!{ DragFloat drag_float_config ImGui_DragFloat 'v (f32) (&'v mut f32) (std::convert::identity)}
-decl_builder_drag!{ DragFloat2 drag_float_2_config ImGui_DragFloat2 'v (f32) (&'v mut [f32; 2]) (<[f32]>::as_mut_ptr)}
-decl_builder_drag!{ DragFloat3 drag_float_3_config ImGui_DragFloat3 'v (f32) (&'v mut [f32; 3]) (<[f32]>::as_mut_ptr)}
-decl_builder_drag!{ DragFloat4 drag_float_4_config ImGui_DragFloat4 'v (f32) (&'v mut [f32; 4]) (<[f32]>::as_mut_ptr)}
+decl_builder_drag!{ DragFloat drag_float_config ImGui_DragFloat 'v (f32) (&'v mut f32) (std::convert::identity) ("%.3f")}
+decl_builder_drag!{ DragFloat2 drag_float_2_config ImGui_DragFloat2 'v (f32) (&'v mut [f32; 2]) (<[f32]>::as_mut_ptr) ("%.3f")}
+decl_builder_drag!{ DragFloat3 drag_float_3_config ImGui_DragFloat3 'v (f32) (&'v mut [f32; 3]) (<[f32]>::as_mut_ptr) ("%.3f")}
+decl_builder_drag!{ DragFloat4 drag_float_4_config ImGui_DragFloat4 'v (f32) (&'v mut [f32; 4]) (<[f32]>::as_mut_ptr) ("%.3f")}
 
 impl_float_format!{ DragFloat }
 impl_float_format!{ DragFloat2 }
 impl_float_format!{ DragFloat3 }
 impl_float_format!{ DragFloat4 }
 
-decl_builder_drag!{ DragInt drag_int_config ImGui_DragInt 'v (i32) (&'v mut i32) (std::convert::identity)}
-decl_builder_drag!{ DragInt2 drag_int_2_config ImGui_DragInt2 'v (i32) (&'v mut [i32; 2]) (<[i32]>::as_mut_ptr)}
-decl_builder_drag!{ DragInt3 drag_int_3_config ImGui_DragInt3 'v (i32) (&'v mut [i32; 3]) (<[i32]>::as_mut_ptr)}
-decl_builder_drag!{ DragInt4 drag_int_4_config ImGui_DragInt4 'v (i32) (&'v mut [i32; 4]) (<[i32]>::as_mut_ptr)}
+decl_builder_drag!{ DragInt drag_int_config ImGui_DragInt 'v (i32) (&'v mut i32) (std::convert::identity) ("%d")}
+decl_builder_drag!{ DragInt2 drag_int_2_config ImGui_DragInt2 'v (i32) (&'v mut [i32; 2]) (<[i32]>::as_mut_ptr) ("%d")}
+decl_builder_drag!{ DragInt3 drag_int_3_config ImGui_DragInt3 'v (i32) (&'v mut [i32; 3]) (<[i32]>::as_mut_ptr) ("%d")}
+decl_builder_drag!{ DragInt4 drag_int_4_config ImGui_DragInt4 'v (i32) (&'v mut [i32; 4]) (<[i32]>::as_mut_ptr) ("%d")}
+
+macro_rules! decl_builder_drag_range {
+    ($name:ident $func:ident $cfunc:ident $life:lifetime ($argty:ty) ($format:literal)) => {
+        decl_builder! { $name -> bool, $cfunc ($life) (S: IntoCStr)
+            (
+                label (S::Temp) (label.as_ptr()),
+                current_min (&$life mut $argty) (current_min),
+                current_max (&$life mut $argty) (current_max),
+                speed (f32) (speed),
+                min ($argty) (min),
+                max ($argty) (max),
+                format (Cow<'static, CStr>) (format.as_ptr()),
+                format_max (Option<Cow<'static, CStr>>) (optional_str(&format_max)),
+                flags (SliderFlags) (flags.bits()),
+            )
+            {
+                decl_builder_setter!{speed: f32}
+                pub fn range(mut self, min: $argty, max: $argty) -> Self {
+                    self.min = min;
+                    self.max = max;
+                    self
+                }
+                decl_builder_setter!{flags: SliderFlags}
+                /// Sets the `sprintf` format used to display the value.
+                pub fn format(mut self, format: impl Into<Cow<'static, CStr>>) -> Self {
+                    self.format = format.into();
+                    self
+                }
+            }
+            {
+                /// A pair of drag widgets to edit a range.
+                ///
+                /// Dear ImGui keeps `current_min <= current_max`: each value is dragged within
+                /// the limits set by the other one.
+                pub fn $func<$life, S: IntoCStr>(&self, label: S, current_min: &$life mut $argty, current_max: &$life mut $argty) -> $name<$life, S> {
+                    $name {
+                        label: label.into(),
+                        current_min,
+                        current_max,
+                        speed: 1.0,
+                        min: <$argty>::default(),
+                        max: <$argty>::default(),
+                        format: Cow::Borrowed(cstr!($format)),
+                        format_max: None,
+                        flags: SliderFlags::None,
+                    }
+                }
+            }
+        }
+    };
+}
+
+decl_builder_drag_range!{ DragFloatRange2 drag_float_range_2_config ImGui_DragFloatRange2 'v (f32) ("%.3f")}
+decl_builder_drag_range!{ DragIntRange2 drag_int_range_2_config ImGui_DragIntRange2 'v (i32) ("%d")}
+
+impl_float_format!{ DragFloatRange2 }
 
 macro_rules! decl_builder_slider {
-    ($name:ident $func:ident $cfunc:ident $life:lifetime ($argty:ty) ($ty:ty) ($expr:expr)) => {
+    ($name:ident $func:ident $cfunc:ident $life:lifetime ($argty:ty) ($ty:ty) ($expr:expr) ($format:literal)) => {
         decl_builder! { $name -> bool, $cfunc ($life) (S: IntoCStr)
             (
                 label (S::Temp) (label.as_ptr()),
@@ -1226,6 +2001,11 @@ macro_rules! decl_builder_slider {
                     self
                 }
                 decl_builder_setter!{flags: SliderFlags}
+                /// Sets the `sprintf` format used to display the value.
+                pub fn format(mut self, format: impl Into<Cow<'static, CStr>>) -> Self {
+                    self.format = format.into();
+                    self
+                }
             }
             {
                 pub fn $func<$life, S: IntoCStr>(&self, label: S, value: $ty) -> $name<$life, S> {
@@ -1234,7 +2014,7 @@ macro_rules! decl_builder_slider {
                         value,
                         min: <$argty>::default(),
                         max: <$argty>::default(),
-                        format: Cow::Borrowed(cstr!("%.3f")),
+                        format: Cow::Borrowed(cstr!($format)),
                         flags: SliderFlags::None,
                     }
                 }
@@ -1243,20 +2023,20 @@ macro_rules! decl_builder_slider {
     };
 }
 
-decl_builder_slider!{ SliderFloat slider_float_config ImGui_SliderFloat 'v (f32) (&'v mut f32) (std::convert::identity)}
-decl_builder_slider!{ SliderFloat2 slider_float_2_config ImGui_SliderFloat2 'v (f32) (&'v mut [f32; 2]) (<[f32]>::as_mut_ptr)}
-decl_builder_slider!{ SliderFloat3 slider_float_3_config ImGui_SliderFloat3 'v (f32) (&'v mut [f32; 3]) (<[f32]>::as_mut_ptr)}
-decl_builder_slider!{ SliderFloat4 slider_float_4_config ImGui_SliderFloat4 'v (f32) (&'v mut [f32; 4]) (<[f32]>::as_mut_ptr)}
+decl_builder_slider!{ SliderFloat slider_float_config ImGui_SliderFloat 'v (f32) (&'v mut f32) (std::convert::identity) ("%.3f")}
+decl_builder_slider!{ SliderFloat2 slider_float_2_config ImGui_SliderFloat2 'v (f32) (&'v mut [f32; 2]) (<[f32]>::as_mut_ptr) ("%.3f")}
+decl_builder_slider!{ SliderFloat3 slider_float_3_config ImGui_SliderFloat3 'v (f32) (&'v mut [f32; 3]) (<[f32]>::as_mut_ptr) ("%.3f")}
+decl_builder_slider!{ SliderFloat4 slider_float_4_config ImGui_SliderFloat4 'v (f32) (&'v mut [f32; 4]) (<[f32]>::as_mut_ptr) ("%.3f")}
 
 impl_float_format!{ SliderFloat }
 impl_float_format!{ SliderFloat2 }
 impl_float_format!{ SliderFloat3 }
 impl_float_format!{ SliderFloat4 }
 
-decl_builder_slider!{ SliderInt slider_int_config ImGui_SliderInt 'v (i32) (&'v mut i32) (std::convert::identity)}
-decl_builder_slider!{ SliderInt2 slider_int_2_config ImGui_SliderInt2 'v (i32) (&'v mut [i32; 2]) (<[i32]>::as_mut_ptr)}
-decl_builder_slider!{ SliderInt3 slider_int_3_config ImGui_SliderInt3 'v (i32) (&'v mut [i32; 3]) (<[i32]>::as_mut_ptr)}
-decl_builder_slider!{ SliderInt4 slider_int_4_config ImGui_SliderInt4 'v (i32) (&'v mut [i32; 4]) (<[i32]>::as_mut_ptr)}
+decl_builder_slider!{ SliderInt slider_int_config ImGui_SliderInt 'v (i32) (&'v mut i32) (std::convert::identity) ("%d")}
+decl_builder_slider!{ SliderInt2 slider_int_2_config ImGui_SliderInt2 'v (i32) (&'v mut [i32; 2]) (<[i32]>::as_mut_ptr) ("%d")}
+decl_builder_slider!{ SliderInt3 slider_int_3_config ImGui_SliderInt3 'v (i32) (&'v mut [i32; 3]) (<[i32]>::as_mut_ptr) ("%d")}
+decl_builder_slider!{ SliderInt4 slider_int_4_config ImGui_SliderInt4 'v (i32) (&'v mut [i32; 4]) (<[i32]>::as_mut_ptr) ("%d")}
 
 decl_builder! { SliderAngle -> bool, ImGui_SliderAngle ('v) (S: IntoCStr)
     (
@@ -1391,6 +2171,127 @@ fn text_pre_edit(text: &mut String) {
     text.push('\0');
 }
 
+/// Where to cut a text that does not fit, see [`Ui::text_ellipsis`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Ellipsis {
+    /// Keeps the end of the text: `...long/path/file.txt`.
+    Start,
+    /// Keeps both ends of the text: `/home/.../file.txt`, useful for paths.
+    Middle,
+    /// Keeps the start of the text: `/home/user/lo...`.
+    End,
+}
+
+/// An element of a simple declarative layout, see [`Ui::layout`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LayoutItem<'a> {
+    Text(&'a str),
+    Button(&'a str),
+    Separator,
+    Spacing,
+    /// Vertical gap of the given height, in pixels.
+    Gap(f32),
+    /// The next item goes in the same line as the previous one.
+    SameLine,
+    NewLine,
+}
+
+// The default font has no '…' glyph, so use three dots
+const ELLIPSIS: &str = "...";
+
+// Shortens `text` with `ELLIPSIS` so that `measure` is not greater than `max_width`.
+fn ellipsize<'a>(text: &'a str, max_width: f32, mode: Ellipsis, measure: impl Fn(&str) -> f32) -> Cow<'a, str> {
+    if measure(text) <= max_width {
+        return Cow::Borrowed(text);
+    }
+    let bounds: Vec<usize> = text.char_indices().map(|(i, _)| i).chain([text.len()]).collect();
+    let num_chars = bounds.len() - 1;
+    // Builds the text keeping `k` chars in total
+    let build = |k: usize| {
+        let (head, tail) = match mode {
+            Ellipsis::Start => (0, k),
+            Ellipsis::Middle => (k - k / 2, k / 2),
+            Ellipsis::End => (k, 0),
+        };
+        let mut res = String::with_capacity(text.len());
+        res.push_str(&text[.. bounds[head]]);
+        res.push_str(ELLIPSIS);
+        res.push_str(&text[bounds[num_chars - tail] ..]);
+        res
+    };
+    // Binary search of the longest text that fits, the width grows with `k`
+    let (mut lo, mut hi) = (0, num_chars);
+    while lo < hi {
+        let k = (lo + hi + 1) / 2;
+        if measure(&build(k)) <= max_width {
+            lo = k;
+        } else {
+            hi = k - 1;
+        }
+    }
+    Cow::Owned(build(lo))
+}
+
+#[derive(Debug, PartialEq)]
+enum MarkdownSpan<'a> {
+    Text(&'a str),
+    Bold(&'a str),
+    Code(&'a str),
+    Link(&'a str, &'a str),
+}
+
+// Finds the `]` that closes an already open `[`, skipping nested pairs
+fn closing_bracket(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => return Some(i),
+            ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+// Splits a line for `Ui::text_markdown_lite()`
+fn markdown_lite_spans(mut line: &str) -> Vec<MarkdownSpan<'_>> {
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    while pos < line.len() {
+        let rest = &line[pos..];
+        let special = if let Some(bold) = rest.strip_prefix("**") {
+            bold.find("**").map(|end| (MarkdownSpan::Bold(&bold[..end]), 2 + end + 2))
+        } else if let Some(code) = rest.strip_prefix('`') {
+            code.find('`').map(|end| (MarkdownSpan::Code(&code[..end]), 1 + end + 1))
+        } else if let Some(link) = rest.strip_prefix('[') {
+            closing_bracket(link).and_then(|text_end| {
+                let url = link[text_end + 1..].strip_prefix('(')?;
+                url.find(')').map(|url_end| (MarkdownSpan::Link(&link[..text_end], &url[..url_end]), 1 + text_end + 2 + url_end + 1))
+            })
+        } else {
+            None
+        };
+        match special {
+            Some((span, len)) => {
+                if pos > 0 {
+                    spans.push(MarkdownSpan::Text(&line[..pos]));
+                }
+                spans.push(span);
+                line = &line[pos + len..];
+                pos = 0;
+            }
+            None => {
+                pos += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+    }
+    if !line.is_empty() {
+        spans.push(MarkdownSpan::Text(line));
+    }
+    spans
+}
+
 #[inline]
 unsafe fn text_post_edit(text: &mut String) {
     let buf = text.as_mut_vec();
@@ -1463,6 +2364,10 @@ decl_builder! { InputTextMultiline -> bool, input_text_multiline_wrapper ('v) (S
         decl_builder_setter_vector2!{size: Vector2}
     }
     {
+        /// Multi-line text editor over a growable `String`.
+        ///
+        /// A `size` of zero, the default, uses Dear ImGui's default box size.
+        /// Lines are always separated with `\n`, whatever the platform.
         pub fn input_text_multiline_config<'v, S: IntoCStr>(&self, label: S, text: &'v mut String) -> InputTextMultiline<'v, S> {
             InputTextMultiline {
                 label:label.into(),
@@ -1576,6 +2481,165 @@ decl_builder! { InputInt -> bool, ImGui_InputInt ('v) (S: IntoCStr)
     }
 }
 
+mod data_type_private {
+    pub trait Sealed {}
+}
+
+/// A Rust scalar type that can be edited with the `*Scalar` Dear ImGui widgets.
+///
+/// This trait is sealed, it is implemented for all the integer types up to 64 bits and for `f32`
+/// and `f64`.
+pub trait DataType: Copy + data_type_private::Sealed {
+    /// The matching `ImGuiDataType` value.
+    const KIND: ImGuiDataType;
+}
+
+macro_rules! impl_data_type {
+    ($($ty:ty => $kind:ident),* $(,)?) => {
+        $(
+            impl data_type_private::Sealed for $ty {}
+            impl DataType for $ty {
+                const KIND: ImGuiDataType = ImGuiDataType_::$kind.0 as ImGuiDataType;
+            }
+        )*
+    };
+}
+
+impl_data_type! {
+    i8 => ImGuiDataType_S8,
+    u8 => ImGuiDataType_U8,
+    i16 => ImGuiDataType_S16,
+    u16 => ImGuiDataType_U16,
+    i32 => ImGuiDataType_S32,
+    u32 => ImGuiDataType_U32,
+    i64 => ImGuiDataType_S64,
+    u64 => ImGuiDataType_U64,
+    f32 => ImGuiDataType_Float,
+    f64 => ImGuiDataType_Double,
+}
+
+fn optional_scalar<T: DataType>(x: &Option<T>) -> *const c_void {
+    x.as_ref().map_or(null(), |x| x as *const T as *const c_void)
+}
+
+unsafe fn input_scalar_helper<T: DataType>(label: *const c_char, value: &mut T, step: Option<T>, step_fast: Option<T>, format: *const c_char, flags: i32) -> bool {
+    ImGui_InputScalar(label, T::KIND, value as *mut T as *mut c_void, optional_scalar(&step), optional_scalar(&step_fast), format, flags)
+}
+
+unsafe fn drag_scalar_helper<T: DataType>(label: *const c_char, value: &mut T, speed: f32, min: Option<T>, max: Option<T>, format: *const c_char, flags: i32) -> bool {
+    ImGui_DragScalar(label, T::KIND, value as *mut T as *mut c_void, speed, optional_scalar(&min), optional_scalar(&max), format, flags)
+}
+
+unsafe fn slider_scalar_helper<T: DataType>(label: *const c_char, value: &mut T, min: T, max: T, format: *const c_char, flags: i32) -> bool {
+    ImGui_SliderScalar(label, T::KIND, value as *mut T as *mut c_void, &min as *const T as *const c_void, &max as *const T as *const c_void, format, flags)
+}
+
+decl_builder! { InputScalar -> bool, input_scalar_helper ('v) (S: IntoCStr, T: DataType)
+    (
+        label (S::Temp) (label.as_ptr()),
+        value (&'v mut T) (value),
+        step (Option<T>) (step),
+        step_fast (Option<T>) (step_fast),
+        format (Option<Cow<'static, CStr>>) (optional_str(&format)),
+        flags (InputTextFlags) (flags.bits()),
+    )
+    {
+        decl_builder_setter!{flags: InputTextFlags}
+        decl_builder_setter!{step: T}
+        decl_builder_setter!{step_fast: T}
+        /// Sets the `sprintf` format, by default it depends on the type of the value.
+        pub fn format(mut self, format: impl Into<Cow<'static, CStr>>) -> Self {
+            self.format = Some(format.into());
+            self
+        }
+    }
+    {
+        /// An input widget for any scalar type, see [`DataType`].
+        pub fn input_scalar_config<'v, S: IntoCStr, T: DataType>(&self, label: S, value: &'v mut T) -> InputScalar<'v, S, T> {
+            InputScalar {
+                label: label.into(),
+                value,
+                step: None,
+                step_fast: None,
+                format: None,
+                flags: InputTextFlags::None,
+            }
+        }
+    }
+}
+
+decl_builder! { DragScalar -> bool, drag_scalar_helper ('v) (S: IntoCStr, T: DataType)
+    (
+        label (S::Temp) (label.as_ptr()),
+        value (&'v mut T) (value),
+        speed (f32) (speed),
+        min (Option<T>) (min),
+        max (Option<T>) (max),
+        format (Option<Cow<'static, CStr>>) (optional_str(&format)),
+        flags (SliderFlags) (flags.bits()),
+    )
+    {
+        decl_builder_setter!{speed: f32}
+        pub fn range(mut self, min: T, max: T) -> Self {
+            self.min = Some(min);
+            self.max = Some(max);
+            self
+        }
+        decl_builder_setter!{flags: SliderFlags}
+        /// Sets the `sprintf` format, by default it depends on the type of the value.
+        pub fn format(mut self, format: impl Into<Cow<'static, CStr>>) -> Self {
+            self.format = Some(format.into());
+            self
+        }
+    }
+    {
+        /// A drag widget for any scalar type, see [`DataType`].
+        pub fn drag_scalar_config<'v, S: IntoCStr, T: DataType>(&self, label: S, value: &'v mut T) -> DragScalar<'v, S, T> {
+            DragScalar {
+                label: label.into(),
+                value,
+                speed: 1.0,
+                min: None,
+                max: None,
+                format: None,
+                flags: SliderFlags::None,
+            }
+        }
+    }
+}
+
+decl_builder! { SliderScalar -> bool, slider_scalar_helper ('v) (S: IntoCStr, T: DataType)
+    (
+        label (S::Temp) (label.as_ptr()),
+        value (&'v mut T) (value),
+        min (T) (min),
+        max (T) (max),
+        format (Option<Cow<'static, CStr>>) (optional_str(&format)),
+        flags (SliderFlags) (flags.bits()),
+    )
+    {
+        decl_builder_setter!{flags: SliderFlags}
+        /// Sets the `sprintf` format, by default it depends on the type of the value.
+        pub fn format(mut self, format: impl Into<Cow<'static, CStr>>) -> Self {
+            self.format = Some(format.into());
+            self
+        }
+    }
+    {
+        /// A slider widget for any scalar type, see [`DataType`].
+        pub fn slider_scalar_config<'v, S: IntoCStr, T: DataType>(&self, label: S, value: &'v mut T, min: T, max: T) -> SliderScalar<'v, S, T> {
+            SliderScalar {
+                label: label.into(),
+                value,
+                min,
+                max,
+                format: None,
+                flags: SliderFlags::None,
+            }
+        }
+    }
+}
+
 macro_rules! decl_builder_input_f {
     ($name:ident $func:ident $cfunc:ident $len:literal) => {
         decl_builder! { $name -> bool, $cfunc ('v) (S: IntoCStr)
@@ -1661,22 +2725,41 @@ decl_builder_with_opt!{Menu, ImGui_BeginMenu, ImGui_EndMenu () (S: IntoCStr)
     }
 }
 
-decl_builder_with_opt!{CollapsingHeader, ImGui_CollapsingHeader, no_op () (S: IntoCStr)
+decl_builder_with_opt!{CollapsingHeader, ImGui_CollapsingHeader1, no_op ('o) (S: IntoCStr)
     (
         label (S::Temp) (label.as_ptr()),
+        opened (Option<&'o mut bool>) (optional_mut_bool(&mut opened)),
         flags (TreeNodeFlags) (flags.bits()),
     )
     {
         decl_builder_setter!{flags: TreeNodeFlags}
+        /// Shows a close button in the header.
+        ///
+        /// When it is clicked `opened` is set to `false`. Then the header is not shown at all,
+        /// until you set it to `true` again.
+        pub fn opened(mut self, opened: &'o mut bool) -> Self {
+            self.opened = Some(opened);
+            self
+        }
     }
     {
         pub fn collapsing_header_config<S: IntoCStr>(&self, label: S) -> CollapsingHeader<S> {
             CollapsingHeader {
                 label: label.into(),
+                opened: None,
                 flags: TreeNodeFlags::None,
                 push: (),
             }
         }
+        /// Helper function for a collapsing header that does not scope its content.
+        ///
+        /// Returns `true` if it is open.
+        pub fn collapsing_header<S: IntoCStr>(&self, label: S, flags: TreeNodeFlags) -> bool {
+            self.collapsing_header_config(label)
+                .flags(flags)
+                .with(|| ())
+                .is_some()
+        }
     }
 }
 
@@ -1696,13 +2779,27 @@ unsafe fn tree_node_ex_helper<S: IntoCStr, H: Hashable>(label_id: LabelId<'_, S,
     }
 }
 
-decl_builder_with_opt!{TreeNode, tree_node_ex_helper, ImGui_TreePop ('a) (S: IntoCStr, H: Hashable)
+unsafe fn tree_pop_helper(flags: TreeNodeFlags) {
+    // With this flag an open node is not pushed, so it must not be popped either
+    if !flags.contains(TreeNodeFlags::NoTreePushOnOpen) {
+        ImGui_TreePop();
+    }
+}
+
+decl_builder_with_opt!{TreeNode, tree_node_ex_helper, tree_pop_helper [flags] ('a) (S: IntoCStr, H: Hashable)
     (
         label (LabelId<'a, S, H>) (label),
         flags (TreeNodeFlags) (flags),
     )
     {
-        decl_builder_setter!{flags: TreeNodeFlags}
+        /// Sets the flags of the tree node.
+        ///
+        /// With `TreeNodeFlags::NoTreePushOnOpen` the closure given to `with` still runs when the
+        /// node is open, but its contents are not indented and `TreePop` is not called.
+        pub fn flags(mut self, flags: TreeNodeFlags) -> Self {
+            self.flags = flags;
+            self
+        }
     }
     {
         pub fn tree_node_config<S: IntoCStr>(&self, label: S) -> TreeNode<'static, S, usize> {
@@ -1813,7 +2910,7 @@ decl_builder_with_opt!{Combo, ImGui_BeginCombo, ImGui_EndCombo () (S1: IntoCStr,
             Combo {
                 label: self.label,
                 preview_value: preview_value.map(|x| x.into()),
-                flags: ComboFlags::None,
+                flags: self.flags,
                 push: (),
             }
         }
@@ -1822,6 +2919,11 @@ decl_builder_with_opt!{Combo, ImGui_BeginCombo, ImGui_EndCombo () (S1: IntoCStr,
         }
     }
     {
+        /// Builds a combo box with arbitrary content.
+        ///
+        /// Draw the dropdown contents, such as separators or custom selectables, with
+        /// `ui.combo_config(label).preview_value(name).with(|| ...)`. The closure and
+        /// `EndCombo` are called only if `BeginCombo` returned true.
         pub fn combo_config<'a, S: IntoCStr>(&self, label: S) -> Combo<S, &'a str> {
             Combo {
                 label: label.into(),
@@ -1855,6 +2957,43 @@ decl_builder_with_opt!{Combo, ImGui_BeginCombo, ImGui_EndCombo () (S1: IntoCStr,
                 });
             changed
         }
+        /// Helper function for a combo over a slice of labels, with the selected index in `current`.
+        ///
+        /// If `items` is empty the combo is shown disabled. If `current` is out of range the
+        /// first item is shown, but `current` is not modified until the user picks one.
+        pub fn combo_index<S: AsRef<str>>(
+            &self,
+            label: impl IntoCStr,
+            current: &mut usize,
+            items: &[S],
+        ) -> bool
+        {
+            if items.is_empty() {
+                self.with_disabled(true, || {
+                    self.combo_config(label)
+                        .preview_value("")
+                        .with(|| ());
+                });
+                return false;
+            }
+            let shown = if *current < items.len() { *current } else { 0 };
+            let mut changed = false;
+            self.combo_config(label)
+                .preview_value(items[shown].as_ref())
+                .with(|| {
+                    for (i, item) in items.iter().enumerate() {
+                        if self.selectable_config(item.as_ref())
+                            .selected(i == shown)
+                            .build()
+                            && i != *current
+                        {
+                            *current = i;
+                            changed = true;
+                        }
+                    }
+                });
+            changed
+        }
     }
 }
 
@@ -1961,10 +3100,22 @@ decl_builder_with_opt!{TabItem, ImGui_BeginTabItem, ImGui_EndTabItem ('o) (S: In
             }
         }
     }
-}
-
-impl<A> Ui<A> {
-    // The callback will be callable until the next call to do_frame()
+}
+
+impl<A> Ui<A> {
+    unsafe fn new() -> Ui<A> {
+        Ui {
+            data: std::ptr::null_mut(),
+            generation: ImGui_GetFrameCount() as usize,
+            callbacks: RefCell::new(Vec::new()),
+            pending_atlas: Cell::new(false),
+            timings: RefCell::new(Vec::new()),
+        }
+    }
+    // The callback will be callable until the next call to do_frame(): the callbacks are owned by
+    // the `Ui` value, that is dropped at the end of do_frame(). Callbacks run during `do_ui` get a null
+    // `A` pointer, those run during the rendering get the real one. Any call with an id from an older
+    // frame is discarded by the generation check in `run_callback()`.
     unsafe fn push_callback<X>(&self, mut cb: impl FnMut(*mut A, X) + 'static) -> usize {
         let cb = Box::new(move |data: *mut A, ptr: *mut c_void| {
             let x = ptr as *mut X;
@@ -1989,16 +3140,32 @@ impl<A> Ui<A> {
         };
 
         let mut callbacks = ui.callbacks.borrow_mut();
-        let cb = &mut callbacks[id];
+        // A matching generation with an unknown index would be a bug in this crate
+        debug_assert!(id < callbacks.len(), "callback id {id} out of range");
+        let Some(cb) = callbacks.get_mut(id) else {
+            eprintln!("lost callback");
+            return;
+        };
         // disable the destructor of x, it will be run inside the callback
         let mut x = MaybeUninit::new(x);
-        cb(&mut *ui.data, x.as_mut_ptr() as *mut c_void);
+        // `ui.data` is null during `do_ui`, so do not make a reference out of it
+        cb(ui.data, x.as_mut_ptr() as *mut c_void);
     }
     /// The next time [`CurrentContext::do_frame()`] is called, it will trigger a call to
     /// [`UiBuilder::build_custom_atlas`].
     pub fn invalidate_font_atlas(&self) {
         self.pending_atlas.set(true);
     }
+    /// Measures the wall-clock time spent in `f`.
+    ///
+    /// The time is added to the entry `label` of [`Context::timings`], when the frame is finished.
+    pub fn scoped_timing<R>(&self, label: &str, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let r = f();
+        let elapsed = start.elapsed();
+        self.timings.borrow_mut().push((label.to_owned(), elapsed));
+        r
+    }
 
     pub fn display_size(&self) -> Vector2 {
         unsafe {
@@ -2012,6 +3179,35 @@ impl<A> Ui<A> {
             (*io).DisplayFramebufferScale.x
         }
     }
+    /// The scale from logical coordinates to framebuffer pixels, in both axes.
+    pub fn framebuffer_scale(&self) -> Vector2 {
+        unsafe {
+            let io = ImGui_GetIO();
+            im_to_v2((*io).DisplayFramebufferScale)
+        }
+    }
+    /// Gets the current clip rectangle, as `(min, max)`, in logical coordinates.
+    ///
+    /// It is the intersection of all the active clip rectangles, such as the window area or
+    /// those pushed with `with_clip_rect`.
+    ///
+    /// A custom renderer running in a draw list callback should set it as the GL scissor, using
+    /// `to_framebuffer_coords`, and remembering that in OpenGL the Y axis goes up:
+    /// `glScissor(min.x, fb_height - max.y, max.x - min.x, max.y - min.y)`.
+    pub fn current_clip_rect(&self) -> (Vector2, Vector2) {
+        unsafe {
+            let clip = (*ImGui_GetWindowDrawList())._CmdHeader.ClipRect;
+            (vec2(clip.x, clip.y), vec2(clip.z, clip.w))
+        }
+    }
+    /// Converts a position in logical coordinates, as used by the UI, to framebuffer pixels.
+    ///
+    /// Useful to do custom rendering inside a draw list callback, in HiDPI displays.
+    pub fn to_framebuffer_coords(&self, pos: Vector2) -> Vector2 {
+        let scale = self.framebuffer_scale();
+        let origin = unsafe { im_to_v2((*ImGui_GetMainViewport()).Pos) };
+        vec2((pos.x - origin.x) * scale.x, (pos.y - origin.y) * scale.y)
+    }
     pub fn get_clipboard_text(&self) -> String {
         unsafe {
             CStr::from_ptr(ImGui_GetClipboardText()).to_string_lossy().into_owned()
@@ -2081,8 +3277,25 @@ impl<A> Ui<A> {
             disabled (bool) (disabled),
         )
     }
+    /// Like [`Ui::with_disabled`] but with a custom `DisabledAlpha` style.
+    ///
+    /// The alpha is applied only by the outermost disabled block, so in nested blocks the
+    /// `alpha` of the inner ones has no effect.
+    pub fn with_disabled_alpha<R>(&self, disabled: bool, alpha: f32, f: impl FnOnce() -> R) -> R {
+        self.with_push((StyleVar::DisabledAlpha, StyleValue::F32(alpha)), || {
+            self.with_disabled(disabled, f)
+        })
+    }
     with_begin_end!{
         /// See `PushClipRect`, `PopClipRect`.
+        ///
+        /// The clip rectangle is pushed into the clip stack of the current window, so it affects both
+        /// the rendering of the widgets and their mouse interaction: items outside the rectangle are
+        /// not hovered nor clicked.
+        ///
+        /// Child windows begun inside are clipped by it too, because their visible area is the
+        /// intersection of their rectangle with the current clip rectangle of the parent.
+        /// Use `intersect_with_current_clip_rect = true` to never draw outside the window area.
         clip_rect ImGui_PushClipRect ImGui_PopClipRect (
             clip_rect_min (Vector2) (&v2_to_im(clip_rect_min)),
             clip_rect_max (Vector2) (&v2_to_im(clip_rect_max)),
@@ -2090,30 +3303,78 @@ impl<A> Ui<A> {
         )
     }
 
+    with_begin_end_opt!{
+        /// See `BeginComboPreview`, `EndComboPreview`.
+        ///
+        /// Draws a custom preview in the last combo box. Call it just after the combo, and that
+        /// combo must have the [`ComboFlags::CustomPreview`] flag. `f` is called only if the
+        /// combo is visible.
+        combo_preview ImGui_BeginComboPreview ImGui_EndComboPreview ()
+    }
     with_begin_end_opt!{
         /// See `BeginMainMenuBar`, `EndMainMenuBar`.
         main_menu_bar ImGui_BeginMainMenuBar ImGui_EndMainMenuBar ()
     }
+    /// Creates a status bar at the bottom of the main viewport, like a main menu bar but at the bottom.
+    ///
+    /// The items inside are laid out horizontally. `f` is only called if the bar is visible.
+    pub fn with_status_bar<R>(&self, f: impl FnOnce() -> R) -> Option<R> {
+        unsafe {
+            let flags = WindowFlags::NoScrollbar | WindowFlags::NoSavedSettings | WindowFlags::MenuBar;
+            // Warning! internal imgui API ahead
+            let visible = ImGui_BeginViewportSideBar(
+                cstr!("##MainStatusBar").as_ptr(),
+                ImGui_GetMainViewport(),
+                Dir::Down.bits(),
+                ImGui_GetFrameHeight(),
+                flags.bits(),
+            );
+            // Same as BeginMainMenuBar, the side bar must be ended even if not visible
+            let r = if visible {
+                self.with_menu_bar(f)
+            } else {
+                None
+            };
+            ImGui_End();
+            r
+        }
+    }
     with_begin_end_opt!{
         /// See `BeginMenuBar`, `EndMenuBar`.
         menu_bar ImGui_BeginMenuBar ImGui_EndMenuBar ()
     }
     with_begin_end_opt!{
         /// See `BeginTooltip`, `EndTooltip`.
+        ///
+        /// `f` is only called if the tooltip is actually shown.
         tooltip ImGui_BeginTooltip ImGui_EndTooltip ()
     }
     with_begin_end_opt!{
         /// See `BeginItemTooltip`, `EndTooltip`. There is not `EndItemTooltip`.
+        ///
+        /// `f` is only called if the last item is hovered and the tooltip is shown.
         item_tooltip ImGui_BeginItemTooltip ImGui_EndTooltip ()
     }
 
     /// Calls the `f` functions with the given `push`
+    ///
+    /// To push several style variables and colors at once use a tuple or an array, see
+    /// [`Pushable`]. They are popped in reverse order when `f` returns, or if it panics:
+    /// `ui.with_push(((StyleVar::FrameRounding, StyleValue::F32(0.0)), (ColorId::Button, Color::TRANSPARENT)), f)`.
     pub fn with_push<R>(&self, push: impl Pushable, f: impl FnOnce() -> R) -> R {
         unsafe {
             let _guard = push_guard(&push);
             f()
         }
     }
+    /// Calls `f` with the given font, scaled by `scale`.
+    ///
+    /// The scale is applied with `SetWindowFontScale`, so the glyphs are stretched from the
+    /// atlas and may look blurry with big scales. If you need the best quality, add the font to
+    /// the atlas with the final size instead.
+    pub fn with_font_scaled<R>(&self, font: FontId, scale: f32, f: impl FnOnce() -> R) -> R {
+        self.with_push((font, WindowFontScale { scale, prev: Cell::new(1.0) }), f)
+    }
     pub fn show_demo_window(&self, mut show: Option<&mut bool>) {
         unsafe {
             ImGui_ShowDemoWindow(optional_mut_bool(&mut show));
@@ -2210,6 +3471,240 @@ impl<A> Ui<A> {
             ImGui_TextWrapped(cstr!("%s").as_ptr(), text.as_ptr())
         }
     }
+    /// Draws `text` inside the rectangle `p_min`-`p_max`, clipped to it.
+    ///
+    /// `align` is the alignment of the text inside the rectangle: (0,0) is top-left, (0.5, 0.5) is centered, (1,1) is bottom-right.
+    /// This does not submit an item, so it will not move the cursor or affect the layout.
+    pub fn text_clipped(&self, p_min: Vector2, p_max: Vector2, text: &str, align: Vector2) {
+        unsafe {
+            let (start, end) = text_ptrs(text);
+            // Warning! internal imgui API ahead
+            ImGui_RenderTextClipped(
+                &v2_to_im(p_min),
+                &v2_to_im(p_max),
+                start,
+                end,
+                null(),
+                &v2_to_im(align),
+                null(),
+            );
+        }
+    }
+    /// Draws a sequence of simple items, in order.
+    ///
+    /// Returns the index in `items` of the button clicked, if any.
+    pub fn layout(&self, items: &[LayoutItem<'_>]) -> Option<usize> {
+        let mut clicked = None;
+        for (i, item) in items.iter().enumerate() {
+            match *item {
+                LayoutItem::Text(text) => self.text(text),
+                LayoutItem::Button(label) => {
+                    if self.button(label) {
+                        clicked = Some(i);
+                    }
+                }
+                LayoutItem::Separator => self.separator(),
+                LayoutItem::Spacing => self.spacing(),
+                LayoutItem::Gap(height) => self.vertical_spacer(height),
+                LayoutItem::SameLine => self.same_line(),
+                LayoutItem::NewLine => self.new_line(),
+            }
+        }
+        clicked
+    }
+    /// Draws `text`, shortened with an ellipsis if it is wider than `max_width`.
+    ///
+    /// If even the ellipsis alone does not fit, it is drawn anyway.
+    pub fn text_ellipsis(&self, text: &str, max_width: f32, mode: Ellipsis) {
+        let text = ellipsize(text, max_width, mode, |t| self.calc_text_size(t).x);
+        self.text(&text);
+    }
+    /// Draws text with a minimal markdown-like format.
+    ///
+    /// Only these constructs are supported, anything else is drawn as is:
+    /// * `**bold text**`
+    /// * `` `code` ``
+    /// * `[link text](url)`
+    /// * Line breaks, that start a new line.
+    ///
+    /// The text is not wrapped. Returns the url of the link clicked this frame, if any.
+    pub fn text_markdown_lite<'s>(&self, src: &'s str) -> Option<&'s str> {
+        let mut clicked = None;
+        for line in src.lines() {
+            let spans = markdown_lite_spans(line);
+            if spans.is_empty() {
+                self.new_line();
+                continue;
+            }
+            for (i, span) in spans.into_iter().enumerate() {
+                if i > 0 {
+                    self.same_line_ex(0.0, 0.0);
+                }
+                match span {
+                    MarkdownSpan::Text(text) => {
+                        self.text(text);
+                    }
+                    MarkdownSpan::Bold(text) => {
+                        // There is no bold font, so draw the text twice, one pixel apart
+                        let pos = self.get_cursor_screen_pos();
+                        self.text(text);
+                        self.window_draw_list().add_text(pos + vec2(1.0, 0.0), self.style().color(ColorId::Text), text);
+                    }
+                    MarkdownSpan::Code(text) => {
+                        // There is no monospace font either, so draw it over a frame background
+                        let pos = self.get_cursor_screen_pos();
+                        let size = self.calc_text_size(text);
+                        self.window_draw_list().add_rect_filled(pos, pos + size, self.style().color(ColorId::FrameBg), 0.0, DrawFlags::None);
+                        self.text(text);
+                    }
+                    MarkdownSpan::Link(text, url) => {
+                        let color = self.style().color(ColorId::ButtonHovered);
+                        self.text_colored(color, text);
+                        if self.is_item_hovered() {
+                            self.set_mouse_cursor(MouseCursor::Hand);
+                            let min = self.get_item_rect_min();
+                            let max = self.get_item_rect_max();
+                            self.window_draw_list().add_line(vec2(min.x, max.y), max, color, 1.0);
+                        }
+                        if self.is_item_clicked(MouseButton::Left) {
+                            clicked = Some(url);
+                        }
+                    }
+                }
+            }
+        }
+        clicked
+    }
+    /// Draws several lines of text, separated by `line_spacing` pixels instead of the usual item spacing.
+    ///
+    /// Each line advances the cursor by `get_font_size() + line_spacing`.
+    pub fn text_lines(&self, lines: &[&str], line_spacing: f32) {
+        let spacing = vec2(self.style().item_spacing().x, line_spacing);
+        // The group is outside the push, so the item spacing after it is the normal one
+        self.with_group(|| {
+            self.with_push((StyleVar::ItemSpacing, StyleValue::Vec2(spacing)), || {
+                for line in lines {
+                    self.text(line);
+                }
+            });
+        });
+    }
+    /// Draws a disabled "(?)" in the same line, that shows `text` in a tooltip when hovered.
+    ///
+    /// Like the `HelpMarker()` function of the Dear ImGui demo.
+    pub fn help_marker(&self, text: &str) {
+        self.same_line();
+        self.text_disabled(cstr!("(?)"));
+        if self.is_item_hovered_ex(HoveredFlags::DelayShort) {
+            self.with_tooltip(|| {
+                self.with_push(TextWrapPos(self.get_font_size() * 35.0), || {
+                    self.text(text);
+                });
+            });
+        }
+    }
+    /// A draggable splitter between two panels.
+    ///
+    /// It is placed at the current cursor position and it takes all the available space in the
+    /// other direction: if `vertical`, it is a vertical bar that splits the panels left and right,
+    /// if not, it is a horizontal bar that splits them top and bottom.
+    ///
+    /// While dragged, `size1` and `size2` are modified, keeping their sum, but never less than
+    /// `min1` and `min2` respectively. Returns `true` if the sizes changed.
+    pub fn splitter(&self, id: impl IntoCStr, vertical: bool, thickness: f32, size1: &mut f32, size2: &mut f32, min1: f32, min2: f32) -> bool {
+        let avail = self.get_content_region_avail();
+        let size = if vertical { vec2(thickness, avail.y) } else { vec2(avail.x, thickness) };
+        self.invisible_button_config(id)
+            .size(size)
+            .build();
+        let active = self.is_item_active();
+        if active || self.is_item_hovered() {
+            self.set_mouse_cursor(if vertical { MouseCursor::ResizeEW } else { MouseCursor::ResizeNS });
+        }
+        if !active {
+            return false;
+        }
+        let delta = self.io().MouseDelta;
+        let delta = if vertical { delta.x } else { delta.y };
+        let delta = delta.max(min1 - *size1).min(*size2 - min2);
+        if delta == 0.0 {
+            return false;
+        }
+        *size1 += delta;
+        *size2 -= delta;
+        true
+    }
+    /// A rotary knob, with the label below it.
+    ///
+    /// Drag the mouse up or right to increase the value, down or left to decrease it. The value is
+    /// kept between `min` and `max`. Returns `true` if the value changed.
+    pub fn knob(&self, label: impl IntoCStr, value: &mut f32, min: f32, max: f32, radius: f32) -> bool {
+        use std::f32::consts::PI;
+        const ANGLE_MIN: f32 = PI * 0.75;
+        const ANGLE_MAX: f32 = PI * 2.25;
+
+        let label = label.into();
+        let text = label.to_string_lossy();
+        // Like regular widgets, do not show anything after "##"
+        let text = text.split("##").next().unwrap_or_default();
+        let pos = self.get_cursor_screen_pos();
+        let center = pos + vec2(radius, radius);
+        let label_y = radius * 2.0 + self.style().item_inner_spacing().y;
+        self.invisible_button_config(&*label)
+            .size(vec2(radius * 2.0, label_y + self.get_text_line_height()))
+            .build();
+
+        let active = self.is_item_active();
+        let mut changed = false;
+        if active {
+            let delta = self.io().MouseDelta;
+            let step = (max - min) / 200.0;
+            let new_value = (*value + (delta.x - delta.y) * step).max(min).min(max);
+            if new_value != *value {
+                *value = new_value;
+                changed = true;
+            }
+        }
+
+        let t = if max > min { (*value - min) / (max - min) } else { 0.0 };
+        let angle = ANGLE_MIN + (ANGLE_MAX - ANGLE_MIN) * t;
+        let bg = if active {
+            ColorId::FrameBgActive
+        } else if self.is_item_hovered() {
+            ColorId::FrameBgHovered
+        } else {
+            ColorId::FrameBg
+        };
+        let thickness = (radius * 0.15).max(1.0);
+        let dir = vec2(angle.cos(), angle.sin());
+        let draw_list = self.window_draw_list();
+        draw_list.add_circle_filled(center, radius, self.style().color(bg), 0);
+        draw_list.add_polyline(&arc_points(center, radius * 0.8, ANGLE_MIN, angle, 32), self.style().color(ColorId::SliderGrab), DrawFlags::None, thickness);
+        draw_list.add_line(center + dir * (radius * 0.3), center + dir * (radius * 0.8), self.style().color(ColorId::SliderGrabActive), thickness);
+        draw_list.add_text(pos + vec2(0.0, label_y), self.style().color(ColorId::Text), text);
+        changed
+    }
+    /// An animated loading indicator: a rotating arc.
+    ///
+    /// The animation is based on `get_time()`, so it does not depend on the frame rate. It is not
+    /// interactive, so it does not need an id: use `same_line()` and `text()` if you want a label.
+    pub fn spinner(&self, radius: f32, thickness: f32, color: Color) {
+        use std::f32::consts::PI;
+        const NUM_SEGMENTS: usize = 30;
+
+        let pos = self.get_cursor_screen_pos();
+        let center = pos + vec2(radius, radius);
+        self.dummy(vec2(radius * 2.0, radius * 2.0));
+
+        let time = self.get_time() as f32;
+        // The arc grows and shrinks while rotating
+        let start = (time * 1.8).sin().abs() * (NUM_SEGMENTS - 5) as f32;
+        let a_min = 2.0 * PI * start / NUM_SEGMENTS as f32;
+        let a_max = 2.0 * PI * (NUM_SEGMENTS - 3) as f32 / NUM_SEGMENTS as f32;
+        let rotation = time * 8.0;
+        let points = arc_points(center, radius - thickness / 2.0, a_min + rotation, a_max + rotation, NUM_SEGMENTS);
+        self.window_draw_list().add_polyline(&points, color, DrawFlags::None, thickness);
+    }
     pub fn label_text(&self, label: impl IntoCStr, text: impl IntoCStr) {
         let label = label.into();
         let text = text.into();
@@ -2298,11 +3793,15 @@ impl<A> Ui<A> {
             ImGui_IsItemToggledOpen()
         }
     }
+    /// Is any item hovered?
+    ///
+    /// Useful to disable your own mouse handling, such as camera controls, while over the UI.
     pub fn is_any_item_hovered(&self) -> bool {
         unsafe {
             ImGui_IsAnyItemHovered()
         }
     }
+    /// Is any item active, for example a slider being dragged?
     pub fn is_any_item_active(&self) -> bool {
         unsafe {
             ImGui_IsAnyItemActive()
@@ -2333,6 +3832,9 @@ impl<A> Ui<A> {
             ImGui_GetItemID()
         }
     }
+    /// Computes the id that Dear ImGui would assign to `id` in the current id stack.
+    ///
+    /// It can be a `&str`, a `usize` or a pointer.
     pub fn get_id(&self, id: impl Hashable) -> ImGuiID {
         unsafe {
             id.get_id()
@@ -2445,11 +3947,15 @@ impl<A> Ui<A> {
             ImGui_SetScrollFromPosY(local_y, center_y_ratio);
         }
     }
+    /// Moves the current window, from inside its own `Begin`/`End`.
+    ///
+    /// Prefer [`Ui::set_next_window_pos`], that avoids a frame of lag.
     pub fn set_window_pos(&self, pos: Vector2, cond: Cond) {
         unsafe {
             ImGui_SetWindowPos(&v2_to_im(pos), cond.bits());
         }
     }
+    /// Resizes the current window, from inside its own `Begin`/`End`.
     pub fn set_window_size(&self, size: Vector2, cond: Cond) {
         unsafe {
             ImGui_SetWindowSize(&v2_to_im(size), cond.bits());
@@ -2465,16 +3971,50 @@ impl<A> Ui<A> {
             ImGui_SetWindowFocus();
         }
     }
+    /// Focuses the window with the given name, it will be effective the next frame.
+    pub fn set_window_focus_by_name(&self, name: impl IntoCStr) {
+        let name = name.into();
+        unsafe {
+            ImGui_SetWindowFocus1(name.as_ptr());
+        }
+    }
+    /// Activates the item with the given id, as if it was clicked or activated with the keyboard.
+    ///
+    /// The item must be submitted the next frame for this to take effect.
+    pub fn activate_item_by_id(&self, id: ImGuiID) {
+        unsafe {
+            // Warning! internal imgui API ahead
+            ImGui_ActivateItemByID(id);
+        }
+    }
+    /// Shows or hides the keyboard navigation cursor, the highlight around the nav item.
+    pub fn set_nav_cursor_visible(&self, visible: bool) {
+        unsafe {
+            // Warning! internal imgui API ahead
+            (*ImGui_GetCurrentContext()).NavDisableHighlight = !visible;
+        }
+    }
     pub fn same_line(&self) {
         unsafe {
             ImGui_SameLine(0.0, -1.0);
         }
     }
+    /// Places the next item in the same line, with the given spacing.
+    ///
+    /// * `offset_from_start_x`: position relative to the window content start, or 0.0 to place
+    ///   it right after the previous item.
+    /// * `spacing`: gap after the previous item, in pixels. A negative value, usually `-1.0`, means
+    ///   the default `ItemSpacing.x` from the style. Note that `0.0` means no gap at all, see
+    ///   [`Ui::same_line_tight`].
     pub fn same_line_ex(&self, offset_from_start_x: f32, spacing: f32) {
         unsafe {
             ImGui_SameLine(offset_from_start_x, spacing);
         }
     }
+    /// Places the next item in the same line, touching the previous one.
+    pub fn same_line_tight(&self) {
+        self.same_line_ex(0.0, 0.0);
+    }
     pub fn new_line(&self) {
         unsafe {
             ImGui_NewLine();
@@ -2490,6 +4030,18 @@ impl<A> Ui<A> {
             ImGui_Dummy(&v2_to_im(size));
         }
     }
+    /// Adds a vertical gap of `height` pixels, plus the usual item spacing.
+    pub fn vertical_spacer(&self, height: f32) {
+        self.dummy(vec2(0.0, height));
+    }
+    /// Adds a horizontal gap of `width` pixels, the next item will be in the same line.
+    ///
+    /// The next item goes right after the gap, without the usual item spacing, so the gap is
+    /// exactly `width`.
+    pub fn horizontal_spacer(&self, width: f32) {
+        self.dummy(vec2(width, 0.0));
+        self.same_line_ex(0.0, 0.0);
+    }
     pub fn indent(&self, indent_w: f32) {
         unsafe {
             ImGui_Indent(indent_w);
@@ -2500,6 +4052,19 @@ impl<A> Ui<A> {
             ImGui_Unindent(indent_w);
         }
     }
+    /// Calls `f` with the tab stop of the items enabled or disabled.
+    ///
+    /// With `allow == false` the items are skipped when navigating with Tab/Shift+Tab, but they
+    /// can still be clicked. Same as `with_push(TabStop(allow), f)`.
+    pub fn with_tab_stop<R>(&self, allow: bool, f: impl FnOnce() -> R) -> R {
+        self.with_push(TabStop(allow), f)
+    }
+    /// Calls `f` with the content indented by `indent_w`.
+    ///
+    /// The indentation is undone afterwards, even if `f` panics.
+    pub fn with_indent<R>(&self, indent_w: f32, f: impl FnOnce() -> R) -> R {
+        self.with_push(Indent(indent_w), f)
+    }
     pub fn get_cursor_pos(&self) -> Vector2 {
         unsafe {
             im_to_v2(ImGui_GetCursorPos())
@@ -2657,11 +4222,15 @@ impl<A> Ui<A> {
             ImGui_GetMouseClickedCount(button.bits())
         }
     }
+    /// Checks if a rectangle of `size` at the cursor position is not clipped.
+    ///
+    /// Use it to skip drawing custom items that are scrolled out of view.
     pub fn is_rect_visible_size(&self, size: Vector2) -> bool {
         unsafe {
             ImGui_IsRectVisible(&v2_to_im(size))
         }
     }
+    /// Checks if a rectangle, in screen coordinates, is not clipped.
     pub fn is_rect_visible(&self, rect_min: Vector2, rect_max: Vector2) -> bool {
         unsafe {
             ImGui_IsRectVisible1(&v2_to_im(rect_min), &v2_to_im(rect_max))
@@ -2678,6 +4247,7 @@ impl<A> Ui<A> {
             ImGui_IsMousePosValid(const ImVec2* mouse_pos = NULL);
         }
     }*/
+    /// Is any mouse button held down?
     pub fn is_any_mouse_down(&self) -> bool {
         unsafe {
             ImGui_IsAnyMouseDown()
@@ -2703,6 +4273,17 @@ impl<A> Ui<A> {
             im_to_v2(ImGui_GetMouseDragDelta(button.bits(), /*lock_threshold*/ -1.0))
         }
     }
+    /// Position of the mouse when `button` was last clicked.
+    ///
+    /// Useful with `get_mouse_drag_delta` to know where a drag started.
+    /// Returns `None` if `button` is not a valid Dear ImGui mouse button.
+    pub fn get_mouse_clicked_pos(&self, button: MouseButton) -> Option<Vector2> {
+        unsafe {
+            let io = &*ImGui_GetIO();
+            let pos = io.MouseClickedPos.get(usize::try_from(button.bits()).ok()?)?;
+            Some(im_to_v2(*pos))
+        }
+    }
     pub fn reset_mouse_drag_delta(&self, button: MouseButton) {
         unsafe {
             ImGui_ResetMouseDragDelta(button.bits());
@@ -2714,6 +4295,9 @@ impl<A> Ui<A> {
                 .unwrap_or(MouseCursor::None)
         }
     }
+    /// Requests a mouse cursor shape for this frame.
+    ///
+    /// It is reset every frame, so call it every frame while needed, usually when a custom item is hovered.
     pub fn set_mouse_cursor(&self, cursor_type: MouseCursor) {
         unsafe {
             ImGui_SetMouseCursor(cursor_type.bits());
@@ -2754,6 +4338,16 @@ impl<A> Ui<A> {
             ImGui_OpenPopup(str_id.as_ptr(), flags.bits());
         }
     }
+    /// Context menu that opens when right-clicking the empty background of the current window.
+    ///
+    /// It uses `PopupFlags::NoOpenOverItems`, so right-clicking a widget does not open it. A
+    /// [`Ui::popup_context_item_config`] of that widget, if any, opens instead.
+    pub fn with_background_context_menu<R>(&self, str_id: impl IntoCStr, f: impl FnOnce() -> R) -> Option<R> {
+        self.popup_context_window_config()
+            .str_id(str_id)
+            .flags(PopupFlags::MouseButtonRight | PopupFlags::NoOpenOverItems)
+            .with(f)
+    }
     pub fn close_current_popup(&self) {
         unsafe {
             ImGui_CloseCurrentPopup();
@@ -2915,16 +4509,31 @@ impl<A> Ui<A> {
         }
     }
     //SetNextWindowClass(const ImGuiWindowClass* window_class)
-    pub fn get_window_doc_id(&self) -> ImGuiID {
+    /// The dock node id of the current window, or 0 if it is not docked.
+    pub fn get_window_dock_id(&self) -> ImGuiID {
         unsafe {
             ImGui_GetWindowDockID()
         }
     }
+    #[deprecated(note = "use get_window_dock_id")]
+    pub fn get_window_doc_id(&self) -> ImGuiID {
+        self.get_window_dock_id()
+    }
     pub fn is_window_docked(&self) -> bool {
         unsafe {
             ImGui_IsWindowDocked()
         }
     }
+    pub fn get_window_viewport(&self) -> Viewport<'_> {
+        unsafe {
+            Viewport {
+                ptr: &*ImGui_GetWindowViewport()
+            }
+        }
+    }
+    pub fn get_window_viewport_id(&self) -> ImGuiID {
+        self.get_window_viewport().id()
+    }
 }
 
 
@@ -3000,6 +4609,14 @@ impl<'ui, A> FontAtlasMut<'ui, A> {
 
             fc.MergeMode = merge;
 
+            if let Some(name) = &font.name {
+                // Name is a fixed size array, keep the last byte as NUL
+                let len = name.len().min(fc.Name.len() - 1);
+                for (dst, &src) in fc.Name.iter_mut().zip(&name.as_bytes()[..len]) {
+                    *dst = src as c_char;
+                }
+            }
+
             // glyph_ranges must be valid for the duration of the atlas, so do not modify the existing self.fonts.
             // You can add new fonts however, but they will not show unless you call update_altas() again
             let glyph_ranges = if font.char_ranges.is_empty() {
@@ -3012,6 +4629,10 @@ impl<'ui, A> FontAtlasMut<'ui, A> {
                 self.glyph_ranges.push(char_ranges);
                 ptr
             };
+            let mut size = font.size * self.scale;
+            if font.snap_size {
+                size = size.round().max(1.0);
+            }
             let io = ImGui_GetIO();
             match font.ttf {
                 TtfData::Bytes(bytes) => {
@@ -3019,12 +4640,14 @@ impl<'ui, A> FontAtlasMut<'ui, A> {
                         (*io).Fonts,
                         bytes.as_ptr() as *mut _,
                         bytes.len() as i32,
-                        font.size * self.scale,
+                        size,
                         &fc,
                         glyph_ranges
                     );
                 }
                 TtfData::DefaultFont => {
+                    // AddFontDefault takes the size from the config, 13 if 0
+                    fc.SizePixels = size;
                     ImFontAtlas_AddFontDefault((*io).Fonts, &fc);
                 }
             }
@@ -3032,6 +4655,11 @@ impl<'ui, A> FontAtlasMut<'ui, A> {
         }
     }
     /// Adds an image as a substitution for a character in a font.
+    ///
+    /// This is useful to map private-use codepoints, such as `'\u{e000}'`, to custom icons that
+    /// can be drawn as regular text. The `draw` function is called after the atlas is built, to
+    /// fill the `size` pixels reserved for the glyph. Then the glyph can be found with
+    /// [`Ui::find_glyph_no_fallback`].
     pub fn add_custom_rect_font_glyph(
         &mut self,
         font: FontId,
@@ -3134,11 +4762,18 @@ impl FontAtlasPtr<'_> {
 }
 
 #[derive(Debug)]
+/// The data passed to the callback of `set_next_window_size_constraints_callback`.
+///
+/// There is no `user_data` here: the callback is a Rust closure, so it can capture any state it needs.
 pub struct SizeCallbackData<'a> {
     ptr: &'a mut ImGuiSizeCallbackData,
 }
 
 impl SizeCallbackData<'_> {
+    /// The position of the window.
+    ///
+    /// This is read-only: Dear ImGui ignores any change to the position done in this callback,
+    /// use `set_next_window_pos` instead.
     pub fn pos(&self) -> Vector2 {
         im_to_v2(self.ptr.Pos)
     }
@@ -3148,6 +4783,7 @@ impl SizeCallbackData<'_> {
     pub fn desired_size(&self) -> Vector2 {
         im_to_v2(self.ptr.DesiredSize)
     }
+    /// Changes the size of the window. It can be freely modified, for example to snap the size to a grid.
     pub fn set_desired_size(&mut self, sz: Vector2) {
         self.ptr.DesiredSize = v2_to_im(sz);
     }
@@ -3167,12 +4803,78 @@ pub struct WindowDrawList<'ui, A> {
     ptr: *mut ImDrawList,
 }
 
+// The dashes of a line, see `WindowDrawList::add_dashed_line`.
+fn dash_segments(p1: Vector2, p2: Vector2, dash_len: f32, gap_len: f32) -> Vec<(Vector2, Vector2)> {
+    let d = p2 - p1;
+    let len = d.x.hypot(d.y);
+    // Written so that NaN values draw nothing, too
+    if !(len > 0.0 && len.is_finite() && dash_len > 0.0) {
+        return Vec::new();
+    }
+    if dash_len >= len {
+        return vec![(p1, p2)];
+    }
+    // Now `step > 0`, so there is no division by zero
+    let step = dash_len + gap_len.max(0.0);
+    let dir = d / len;
+    // Counted beforehand, because adding a small step to a big `t` may not change it
+    let count = ((len / step).ceil() as usize).max(1);
+    (0 .. count)
+        .map(|i| {
+            let t = i as f32 * step;
+            let end = (t + dash_len).min(len);
+            (p1 + dir * t, p1 + dir * end)
+        })
+        .collect()
+}
+
 impl<'ui, A> WindowDrawList<'ui, A> {
     pub fn add_line(&self, p1: Vector2, p2: Vector2, color: Color, thickness: f32) {
         unsafe {
             ImDrawList_AddLine(self.ptr, &v2_to_im(p1), &v2_to_im(p2), color.as_u32(), thickness);
         }
     }
+    /// Draws a dashed line from `p1` to `p2`: segments of `dash_len` pixels separated by `gap_len` pixels.
+    ///
+    /// Nothing is drawn if `dash_len` is not positive. A negative `gap_len` is taken as 0.
+    pub fn add_dashed_line(&self, p1: Vector2, p2: Vector2, color: Color, thickness: f32, dash_len: f32, gap_len: f32) {
+        let color = color.as_u32();
+        // Half a pixel to hit the pixel centers, as `AddLine` does
+        let half = vec2(0.5, 0.5);
+        for (a, b) in dash_segments(p1, p2, dash_len, gap_len) {
+            let points = [v2_to_im(a + half), v2_to_im(b + half)];
+            // This is the body of `PathLineTo` + `PathStroke`, that are inline, without copying the points to the path
+            unsafe {
+                ImDrawList_AddPolyline(self.ptr, points.as_ptr(), 2, color, 0, thickness);
+            }
+        }
+    }
+    /// Draws an arrow from `from` to `to`, with a filled triangular head of size `head_size` pointing at `to`.
+    ///
+    /// If both points are the same nothing is drawn.
+    pub fn add_arrow(&self, from: Vector2, to: Vector2, color: Color, thickness: f32, head_size: f32) {
+        let d = to - from;
+        let len = d.x.hypot(d.y);
+        if len <= 0.0 {
+            return;
+        }
+        let dir = d / len;
+        let normal = vec2(-dir.y, dir.x);
+        // Do not let the head go beyond the start of the arrow
+        let head_size = head_size.min(len);
+        let base = to - dir * head_size;
+        self.add_line(from, base, color, thickness);
+        let half_width = normal * (head_size * 0.5);
+        self.add_triangle_filled(to, base + half_width, base - half_width, color);
+    }
+    /// Draws the typical S-shaped link of a node editor, from an output pin at `from` to an input pin at `to`.
+    ///
+    /// It is a cubic bezier with horizontal tangents at both ends, the control points are
+    /// separated from the ends by half the horizontal distance.
+    pub fn add_node_link(&self, from: Vector2, to: Vector2, color: Color, thickness: f32) {
+        let offset = vec2((to.x - from.x).abs() * 0.5, 0.0);
+        self.add_bezier_cubic(from, from + offset, to - offset, to, color, thickness, 0);
+    }
     pub fn add_rect(&self, p_min: Vector2, p_max: Vector2, color: Color, rounding: f32, flags: DrawFlags, thickness: f32) {
         unsafe {
             ImDrawList_AddRect(self.ptr, &v2_to_im(p_min), &v2_to_im(p_max), color.as_u32(), rounding, flags.bits(), thickness);
@@ -3183,6 +4885,14 @@ impl<'ui, A> WindowDrawList<'ui, A> {
             ImDrawList_AddRectFilled(self.ptr, &v2_to_im(p_min), &v2_to_im(p_max), color.as_u32(), rounding, flags.bits());
         }
     }
+    /// Draws a filled, semi-transparent rectangle over the current content, such as a selection highlight.
+    ///
+    /// The color uses straight (not premultiplied) alpha: the renderer blends with
+    /// `SRC_ALPHA, ONE_MINUS_SRC_ALPHA`, so `Color::new(0.0, 0.0, 1.0, 0.5)` drawn over opaque white
+    /// gives `(0.5, 0.5, 1.0)`. Do not multiply the RGB components by the alpha yourself.
+    pub fn add_overlay_rect(&self, p_min: Vector2, p_max: Vector2, color: Color) {
+        self.add_rect_filled(p_min, p_max, color, 0.0, DrawFlags::None);
+    }
     pub fn add_rect_filled_multicolor(&self, p_min: Vector2, p_max: Vector2, col_upr_left: Color, col_upr_right: Color, col_bot_right: Color, col_bot_left: Color) {
         unsafe {
             ImDrawList_AddRectFilledMultiColor(self.ptr, &v2_to_im(p_min), &v2_to_im(p_max), col_upr_left.as_u32(), col_upr_right.as_u32(), col_bot_right.as_u32(), col_bot_left.as_u32());
@@ -3208,11 +4918,21 @@ impl<'ui, A> WindowDrawList<'ui, A> {
             ImDrawList_AddTriangleFilled(self.ptr, &v2_to_im(p1), &v2_to_im(p2), &v2_to_im(p3), color.as_u32());
         }
     }
+    /// Draws a circle.
+    ///
+    /// If `num_segments` is 0 the number of segments is computed from the radius, so that the
+    /// error is not greater than the `CircleTessellationMaxError` style. That is usually the best
+    /// choice, see [`WindowDrawList::add_circle_auto`].
     pub fn add_circle(&self, center: Vector2, radius: f32, color: Color, num_segments: i32, thickness: f32) {
         unsafe {
             ImDrawList_AddCircle(self.ptr, &v2_to_im(center), radius, color.as_u32(), num_segments, thickness);
         }
     }
+    /// Draws a circle with the number of segments computed from the radius.
+    pub fn add_circle_auto(&self, center: Vector2, radius: f32, color: Color, thickness: f32) {
+        self.add_circle(center, radius, color, 0, thickness);
+    }
+    /// Draws a filled circle. See [`WindowDrawList::add_circle`] for the meaning of `num_segments`.
     pub fn add_circle_filled(&self, center: Vector2, radius: f32, color: Color, num_segments: i32) {
         unsafe {
             ImDrawList_AddCircleFilled(self.ptr, &v2_to_im(center), radius, color.as_u32(), num_segments);
@@ -3234,6 +4954,17 @@ impl<'ui, A> WindowDrawList<'ui, A> {
             ImDrawList_AddText(self.ptr, &v2_to_im(pos), color.as_u32(), start, end);
         }
     }
+    /// Draws `text` inside the rectangle `p_min`-`p_max`, positioned by `align`.
+    ///
+    /// `align` goes from `(0.0, 0.0)`, top-left, to `(1.0, 1.0)`, bottom-right, so `(0.5, 0.5)` is
+    /// centered. The text is not clipped to the rectangle.
+    pub fn add_text_aligned(&self, p_min: Vector2, p_max: Vector2, color: Color, text: &str, align: Vector2) {
+        let size = self.ui.calc_text_size(text);
+        let free = p_max - p_min - size;
+        let pos = p_min + vec2(free.x * align.x, free.y * align.y);
+        // Round the position to keep the glyphs crisp
+        self.add_text(vec2(pos.x.floor(), pos.y.floor()), color, text);
+    }
     pub fn add_text_ex(&self, font: FontId, font_size: f32, pos: Vector2, color: Color, text: &str, wrap_width: f32, cpu_fine_clip_rect: Option<ImVec4>) {
         unsafe {
             let (start, end) = text_ptrs(text);
@@ -3278,6 +5009,35 @@ impl<'ui, A> WindowDrawList<'ui, A> {
             ImDrawList_AddImageRounded(self.ptr, user_texture_id.id(), &v2_to_im(p_min), &v2_to_im(p_max), &v2_to_im(uv_min), &v2_to_im(uv_max), color.as_u32(), rounding, flags.bits());
         }
     }
+    /// Draws an image with rounded corners and a border around it, such as a thumbnail.
+    ///
+    /// The border is not drawn if `border_thickness` is 0.
+    pub fn add_image_framed(&self, user_texture_id: TextureId, p_min: Vector2, p_max: Vector2, uv_min: Vector2, uv_max: Vector2, rounding: f32, border_color: Color, border_thickness: f32) {
+        self.add_image_rounded(user_texture_id, p_min, p_max, uv_min, uv_max, Color::WHITE, rounding, DrawFlags::None);
+        if border_thickness > 0.0 {
+            self.add_rect(p_min, p_max, border_color, rounding, DrawFlags::None, border_thickness);
+        }
+    }
+    /// Calls `f` with `texture` as the current texture of this draw list.
+    ///
+    /// Use it with [`WindowDrawList::prim_rect_uv`] to draw many sprites of the same texture in a
+    /// single draw command.
+    pub fn with_texture<R>(&self, texture: TextureId, f: impl FnOnce() -> R) -> R {
+        let push = DrawListTexture { ptr: self.ptr, texture };
+        unsafe {
+            let _guard = push_guard(&push);
+            f()
+        }
+    }
+    /// Draws a textured rectangle with the current texture, see [`WindowDrawList::with_texture`].
+    ///
+    /// Unlike `add_image` it does not change the texture, so it never splits the draw command.
+    pub fn prim_rect_uv(&self, p_min: Vector2, p_max: Vector2, uv_min: Vector2, uv_max: Vector2, color: Color) {
+        unsafe {
+            ImDrawList_PrimReserve(self.ptr, 6, 4);
+            ImDrawList_PrimRectUV(self.ptr, &v2_to_im(p_min), &v2_to_im(p_max), &v2_to_im(uv_min), &v2_to_im(uv_max), color.as_u32());
+        }
+    }
 
     pub fn add_callback(&self, cb: impl FnOnce(&mut A) + 'static) {
         // Callbacks are only called once, convert the FnOnce into an FnMut to register
@@ -3301,6 +5061,16 @@ impl<'ui, A> WindowDrawList<'ui, A> {
     }
 }
 
+// Points of an arc, for `add_polyline()`, angles in radians
+fn arc_points(center: Vector2, radius: f32, a_min: f32, a_max: f32, num_segments: usize) -> Vec<ImVec2> {
+    (0..=num_segments)
+        .map(|i| {
+            let a = a_min + (a_max - a_min) * i as f32 / num_segments as f32;
+            im_vec2(center.x + a.cos() * radius, center.y + a.sin() * radius)
+        })
+        .collect()
+}
+
 unsafe extern "C" fn call_drawlist_callback<A>(_parent_lilst: *const ImDrawList, cmd: *const ImDrawCmd) {
     let id = (*cmd).UserCallbackData as usize;
     Ui::<A>::run_callback(id, ());
@@ -3333,6 +5103,16 @@ impl Hashable for usize {
     }
 }
 
+/// Pointers are hashed by address, the pointee is never accessed.
+impl<T: ?Sized> Hashable for *const T {
+    unsafe fn get_id(&self) -> ImGuiID {
+        ImGui_GetID2(self.cast::<c_void>())
+    }
+    unsafe fn push(&self) {
+        ImGui_PushID2(self.cast::<c_void>());
+    }
+}
+
 /// Any value that can be applied with a _push_ function and unapplied with a _pop_ function.
 ///
 /// Apply to the current frame using [`Ui::with_push`]. If you want to apply several values at the
@@ -3450,6 +5230,39 @@ impl Pushable for FontId {
     }
 }
 
+// Multiplies the font scale of the current window, for `Ui::with_font_scaled`.
+struct WindowFontScale {
+    scale: f32,
+    prev: Cell<f32>,
+}
+
+impl Pushable for WindowFontScale {
+    unsafe fn push(&self) {
+        // Warning! internal imgui API ahead
+        let window = (*ImGui_GetCurrentContext()).CurrentWindow;
+        self.prev.set((*window).FontWindowScale);
+        ImGui_SetWindowFontScale(self.prev.get() * self.scale);
+    }
+    unsafe fn pop(&self) {
+        ImGui_SetWindowFontScale(self.prev.get());
+    }
+}
+
+// The current texture of a draw list, for `WindowDrawList::with_texture`.
+struct DrawListTexture {
+    ptr: *mut ImDrawList,
+    texture: TextureId,
+}
+
+impl Pushable for DrawListTexture {
+    unsafe fn push(&self) {
+        ImDrawList_PushTextureID(self.ptr, self.texture.id());
+    }
+    unsafe fn pop(&self) {
+        ImDrawList_PopTextureID(self.ptr);
+    }
+}
+
 pub type StyleColor = (ColorId, Color);
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -3642,6 +5455,9 @@ pub struct Viewport<'s> {
 }
 
 impl Viewport<'_> {
+    pub fn id(&self) -> ImGuiID {
+        self.ptr.ID
+    }
     pub fn flags(&self) -> ViewportFlags {
         ViewportFlags::from_bits_truncate(self.ptr.Flags)
     }
@@ -3713,11 +5529,18 @@ decl_builder_with_opt!{ TableConfig, ImGui_BeginTable, ImGui_EndTable () (S: Int
                 ImGui_TableHeadersRow();
             }
         }
+        pub fn table_header(&self, label: impl IntoCStr) {
+            let label = label.into();
+            unsafe {
+                ImGui_TableHeader(label.as_ptr());
+            }
+        }
         pub fn table_angle_headers_row(&self) {
             unsafe {
                 ImGui_TableAngledHeadersRow();
             }
         }
+        /// The number of columns of the current table, as passed to `table_config`.
         pub fn table_get_columns_count(&self) -> i32 {
             unsafe {
                 ImGui_TableGetColumnCount()
@@ -3733,6 +5556,9 @@ decl_builder_with_opt!{ TableConfig, ImGui_BeginTable, ImGui_EndTable () (S: Int
                 ImGui_TableGetRowIndex()
             }
         }
+        /// Gets the flags of a column, or of the current one if `column_n` is `None`.
+        ///
+        /// Use it to check if a column is `IsEnabled`, `IsVisible`, `IsSorted` or `IsHovered`.
         pub fn table_get_column_flags(&self, column_n: Option<i32>) -> TableColumnFlags {
             let bits = unsafe {
                 ImGui_TableGetColumnFlags(column_n.unwrap_or(-1))
@@ -3744,12 +5570,107 @@ decl_builder_with_opt!{ TableConfig, ImGui_BeginTable, ImGui_EndTable () (S: Int
                 ImGui_TableSetColumnEnabled(column_n.unwrap_or(-1), enabled);
             };
         }
+        /// Changes the background color of a row or a cell.
+        ///
+        /// For `TableBgTarget::CellBg` the cell is that of `column_n` in the current row, or the current
+        /// cell if `None`. For the other targets `column_n` must be `None`.
         pub fn table_set_bg_color(&self, target: TableBgTarget, color: Color, column_n: Option<i32>) {
             unsafe {
                 ImGui_TableSetBgColor(target.bits(), color.as_u32(), column_n.unwrap_or(-1));
             };
         }
-        //TODO: ImGui_TableGetSortSpecs, TableGetColumnName
+        /// Gets the name of a column, or of the current one if `column_n` is `None`.
+        ///
+        /// Returns `None` if there is no current table, or if the column does not exist.
+        pub fn table_get_column_name(&self, column_n: Option<i32>) -> Option<String> {
+            unsafe {
+                // ImGui asserts on a bad index, and there is no current column before the first `table_next_column`
+                let column_n = match column_n {
+                    Some(n) => n,
+                    None => ImGui_TableGetColumnIndex(),
+                };
+                if !(0 .. ImGui_TableGetColumnCount()).contains(&column_n) {
+                    return None;
+                }
+                let name = ImGui_TableGetColumnName(column_n);
+                if name.is_null() {
+                    return None;
+                }
+                Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+            }
+        }
+        /// Gets the sorting specifications of the current table.
+        ///
+        /// Returns `None` if the table is not sortable. Check [`TableSortSpecs::is_dirty`] to know
+        /// if you need to sort your data.
+        pub fn table_get_sort_specs(&self) -> Option<TableSortSpecs<'_>> {
+            unsafe {
+                let ptr = ImGui_TableGetSortSpecs();
+                if ptr.is_null() {
+                    return None;
+                }
+                Some(TableSortSpecs {
+                    ptr,
+                    _pd: PhantomData,
+                })
+            }
+        }
+    }
+}
+
+/// The sorting specifications of a table, see [`Ui::table_get_sort_specs`].
+pub struct TableSortSpecs<'a> {
+    // A raw pointer, because several of these may exist at the same time for the same table
+    ptr: *mut ImGuiTableSortSpecs,
+    _pd: PhantomData<&'a ()>,
+}
+
+impl TableSortSpecs<'_> {
+    /// Returns `true` if the specs have changed since the last time, so the data should be sorted again.
+    pub fn is_dirty(&self) -> bool {
+        unsafe {
+            (*self.ptr).SpecsDirty
+        }
+    }
+    /// Clears the dirty flag, call it after sorting your data.
+    pub fn set_clean(&mut self) {
+        unsafe {
+            (*self.ptr).SpecsDirty = false;
+        }
+    }
+    /// The columns to sort by, in order of priority.
+    ///
+    /// It will usually have only one item, unless the table has `TableFlags::SortMulti`.
+    pub fn iter(&self) -> impl Iterator<Item = TableColumnSortSpec<'_>> {
+        let specs = unsafe {
+            let ptr = &*self.ptr;
+            if ptr.SpecsCount <= 0 {
+                &[]
+            } else {
+                std::slice::from_raw_parts(ptr.Specs, ptr.SpecsCount as usize)
+            }
+        };
+        specs.iter().map(TableColumnSortSpec)
+    }
+}
+
+/// The sorting specification for one column, see [`TableSortSpecs::iter`].
+pub struct TableColumnSortSpec<'a>(&'a ImGuiTableColumnSortSpecs);
+
+impl TableColumnSortSpec<'_> {
+    /// The `user_id` of the column, as set in `table_setup_column`.
+    pub fn id(&self) -> ImGuiID {
+        self.0.ColumnUserID
+    }
+    pub fn index(&self) -> usize {
+        self.0.ColumnIndex as usize
+    }
+    pub fn sort_order(&self) -> usize {
+        self.0.SortOrder as usize
+    }
+    pub fn sort_direction(&self) -> SortDirection {
+        SortDirection::from_bits(self.0.SortDirection.0 as i32)
+            .unwrap_or(SortDirection::None)
     }
 }
 
@@ -3862,5 +5783,74 @@ impl<'a> DragDropPayload<'a> {
     }
 }
 
+/// A fixed capacity history of values, to be drawn as a scrolling line graph.
+///
+/// Once full, pushing a new value discards the oldest one.
+#[derive(Debug, Clone)]
+pub struct ScrollingPlot {
+    values: Vec<f32>,
+    capacity: usize,
+    offset: usize,
+}
+
+impl ScrollingPlot {
+    /// Creates an empty plot that will keep the last `capacity` values.
+    pub fn new(capacity: usize) -> ScrollingPlot {
+        assert!(capacity > 0, "ScrollingPlot capacity must not be 0");
+        ScrollingPlot {
+            values: Vec::with_capacity(capacity),
+            capacity,
+            offset: 0,
+        }
+    }
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.offset = 0;
+    }
+    /// Adds a value, dropping the oldest one if the buffer is full.
+    pub fn push(&mut self, value: f32) {
+        if self.values.len() < self.capacity {
+            self.values.push(value);
+        } else {
+            self.values[self.offset] = value;
+            self.offset = (self.offset + 1) % self.capacity;
+        }
+    }
+    /// Iterates the stored values, from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = f32> + '_ {
+        let (newer, older) = self.values.split_at(self.offset);
+        older.iter().chain(newer).copied()
+    }
+    /// Draws the stored values with `PlotLines`.
+    ///
+    /// If `size.y` is 0, the default height is used.
+    pub fn draw<A>(&self, _ui: &Ui<A>, label: impl IntoCStr, size: Vector2) {
+        let label = label.into();
+        unsafe {
+            // PlotLines handles the ring buffer natively with the values_offset
+            ImGui_PlotLines(
+                label.as_ptr(),
+                self.values.as_ptr(),
+                self.values.len() as i32,
+                self.offset as i32,
+                null(),
+                f32::MAX,
+                f32::MAX,
+                &v2_to_im(size),
+                std::mem::size_of::<f32>() as i32,
+            );
+        }
+    }
+}
+
 pub const PAYLOAD_TYPE_COLOR_3F: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(IMGUI_PAYLOAD_TYPE_COLOR_3F) };
 pub const PAYLOAD_TYPE_COLOR_4F: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(IMGUI_PAYLOAD_TYPE_COLOR_4F) };
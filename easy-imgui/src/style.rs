@@ -92,9 +92,26 @@ impl<'a> StylePtr<'a> {
         c.a *= a * alpha_mul;
         c
     }
+    pub fn window_padding(&self) -> Vector2 {
+        im_to_v2(self.ptr.WindowPadding)
+    }
+    pub fn set_window_padding(&mut self, padding: Vector2) {
+        self.ptr.WindowPadding = v2_to_im(padding);
+    }
+    /// Maximum error, in pixels, allowed when drawing circles with an automatic number of segments.
+    pub fn circle_tessellation_max_error(&self) -> f32 {
+        self.ptr.CircleTessellationMaxError
+    }
+    /// Sets the circle tessellation error. Lower values give smoother circles but more vertices.
+    pub fn set_circle_tessellation_max_error(&mut self, max_error: f32) {
+        self.ptr.CircleTessellationMaxError = max_error;
+    }
     pub fn frame_padding(&self) -> Vector2 {
         im_to_v2(self.ptr.FramePadding)
     }
+    pub fn set_frame_padding(&mut self, padding: Vector2) {
+        self.ptr.FramePadding = v2_to_im(padding);
+    }
     pub fn frame_rounding(&self) -> f32 {
         self.ptr.FrameRounding
     }
@@ -104,7 +121,16 @@ impl<'a> StylePtr<'a> {
     pub fn item_spacing(&self) -> Vector2 {
         im_to_v2(self.ptr.ItemSpacing)
     }
+    pub fn set_item_spacing(&mut self, spacing: Vector2) {
+        self.ptr.ItemSpacing = v2_to_im(spacing);
+    }
     pub fn item_inner_spacing(&self) -> Vector2 {
         im_to_v2(self.ptr.ItemInnerSpacing)
     }
+    pub fn set_item_inner_spacing(&mut self, spacing: Vector2) {
+        self.ptr.ItemInnerSpacing = v2_to_im(spacing);
+    }
+    pub fn indent_spacing(&self) -> f32 {
+        self.ptr.IndentSpacing
+    }
 }
@@ -0,0 +1,777 @@
+use super::*;
+use std::rc::Rc;
+
+// A context with an 800x600 display, to run frames without a window.
+//
+// Dear ImGui keeps the current context in a thread local, so tests can run concurrently.
+struct TestContext {
+    ctx: Context,
+}
+
+impl TestContext {
+    fn new() -> TestContext {
+        TestContext::with_scale(1.0)
+    }
+    fn with_scale(scale: f32) -> TestContext {
+        unsafe {
+            let mut ctx = Context::new();
+            ctx.set_current().set_size(vec2(800.0, 600.0), scale);
+            // Process all the events queued for a frame in that frame, so a press and a release can go together
+            (*ImGui_GetIO()).ConfigInputTrickleEventQueue = false;
+            TestContext { ctx }
+        }
+    }
+    fn current(&mut self) -> CurrentContext<'_> {
+        unsafe { self.ctx.set_current() }
+    }
+    // Queues the input events with `input`, then builds and renders a frame of `app`.
+    fn frame<A: UiBuilder>(&mut self, app: &mut A, input: impl FnOnce(&mut CurrentContext<'_>)) {
+        let mut cur = self.current();
+        input(&mut cur);
+        unsafe {
+            cur.update_atlas(app);
+            cur.do_frame(app, || {}, |_| {});
+        }
+    }
+    // Runs a frame with the mouse at `pos` and the left button pressed or not.
+    fn mouse_frame<A: UiBuilder>(&mut self, app: &mut A, pos: Vector2, down: bool) {
+        self.frame(app, |_| unsafe {
+            let io = ImGui_GetIO();
+            ImGuiIO_AddMousePosEvent(io, pos.x, pos.y);
+            ImGuiIO_AddMouseButtonEvent(io, MouseButton::Left.bits(), down);
+        });
+    }
+    // Clicks at `pos`, hovering there first because the hovered window comes from the previous frame.
+    fn click<A: UiBuilder>(&mut self, app: &mut A, pos: Vector2) {
+        for down in [false, false, true, false] {
+            self.mouse_frame(app, pos, down);
+        }
+    }
+}
+
+// A window that covers the whole display, without decorations.
+fn test_window<A>(ui: &Ui<A>, f: impl FnOnce()) {
+    ui.window_config("test")
+        .position(vec2(0.0, 0.0), Cond::Always)
+        .size(vec2(800.0, 600.0), Cond::Always)
+        .flags(WindowFlags::NoDecoration)
+        .with(f);
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum ClipMode {
+    None,
+    Clip,
+    ClipChild,
+}
+
+struct ClipApp {
+    mode: ClipMode,
+    hovered: bool,
+}
+
+impl UiBuilder for ClipApp {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        let mode = self.mode;
+        let hovered = &mut self.hovered;
+        ui.window_config("test")
+            .position(vec2(0.0, 0.0), Cond::Always)
+            .size(vec2(400.0, 400.0), Cond::Always)
+            .flags(WindowFlags::NoDecoration)
+            .with(|| {
+                let mut button = || {
+                    ui.set_cursor_screen_pos(vec2(100.0, 100.0));
+                    ui.button_config("button").size(vec2(50.0, 50.0)).build();
+                    *hovered = ui.is_item_hovered();
+                };
+                match mode {
+                    ClipMode::None => button(),
+                    ClipMode::Clip => {
+                        ui.with_clip_rect(vec2(0.0, 0.0), vec2(50.0, 50.0), true, button);
+                    }
+                    ClipMode::ClipChild => {
+                        ui.with_clip_rect(vec2(0.0, 0.0), vec2(50.0, 50.0), true, || {
+                            ui.child_config("child").size(vec2(300.0, 300.0)).with(button);
+                        });
+                    }
+                }
+            });
+    }
+}
+
+#[test]
+fn clip_rect_blocks_interaction() {
+    let mouse = vec2(120.0, 120.0);
+    for (mode, expected) in [(ClipMode::None, true), (ClipMode::Clip, false), (ClipMode::ClipChild, false)] {
+        let mut app = ClipApp { mode, hovered: !expected };
+        let mut tc = TestContext::new();
+        for _ in 0..3 {
+            tc.mouse_frame(&mut app, mouse, false);
+        }
+        assert_eq!(app.hovered, expected);
+    }
+}
+
+#[test]
+fn font_info_size() {
+    let ttf = || vec![0u8; 16];
+    assert_eq!(FontInfo::try_new(ttf(), 0.0).err(), Some(FontInfoError::InvalidSize(0.0)));
+    assert_eq!(FontInfo::try_new(ttf(), -3.0).err(), Some(FontInfoError::InvalidSize(-3.0)));
+    assert!(matches!(FontInfo::try_new(ttf(), f32::NAN), Err(FontInfoError::InvalidSize(_))));
+    assert_eq!(FontInfo::try_new(Vec::new(), 13.0).err(), Some(FontInfoError::EmptyTtf));
+    assert_eq!(FontInfo::try_new(ttf(), 13.0).map(|f| f.size), Ok(13.0));
+
+    // The infallible constructors do not check anything
+    assert_eq!(FontInfo::new(Vec::new(), 0.0).size, 0.0);
+    assert_eq!(FontInfo::new(ttf(), 13.0).size, 13.0);
+    assert_eq!(FontInfo::default_font(16.0).size, 16.0);
+}
+
+// Measures a text as one unit per char, so that multi-byte chars count as one.
+fn char_width(s: &str) -> f32 {
+    s.chars().count() as f32
+}
+
+#[test]
+fn ellipsize_modes() {
+    assert_eq!(ellipsize("hello world", 8.0, Ellipsis::End, char_width), "hello...");
+    assert_eq!(ellipsize("hello world", 8.0, Ellipsis::Start, char_width), "...world");
+    assert_eq!(ellipsize("hello world", 8.0, Ellipsis::Middle, char_width), "hel...ld");
+}
+
+#[test]
+fn ellipsize_fits() {
+    for mode in [Ellipsis::Start, Ellipsis::Middle, Ellipsis::End] {
+        let res = ellipsize("hello", 5.0, mode, char_width);
+        assert!(matches!(res, Cow::Borrowed("hello")));
+    }
+}
+
+#[test]
+fn ellipsize_narrower_than_ellipsis() {
+    for mode in [Ellipsis::Start, Ellipsis::Middle, Ellipsis::End] {
+        assert_eq!(ellipsize("hello world", 2.0, mode, char_width), "...");
+    }
+}
+
+#[test]
+fn ellipsize_multi_byte() {
+    assert_eq!(ellipsize("ñandú über", 7.0, Ellipsis::End, char_width), "ñand...");
+    assert_eq!(ellipsize("ñandú über", 7.0, Ellipsis::Start, char_width), "...über");
+    assert_eq!(ellipsize("ñandú über", 7.0, Ellipsis::Middle, char_width), "ña...er");
+    // Measuring bytes instead of chars must still cut at char boundaries
+    assert_eq!(ellipsize("ñandú", 6.0, Ellipsis::End, |s| s.len() as f32), "ña...");
+}
+
+#[test]
+fn markdown_lite_constructs() {
+    use MarkdownSpan::*;
+    assert!(markdown_lite_spans("").is_empty());
+    assert_eq!(markdown_lite_spans("plain text"), vec![Text("plain text")]);
+    assert_eq!(markdown_lite_spans("a **bold** b"), vec![Text("a "), Bold("bold"), Text(" b")]);
+    assert_eq!(markdown_lite_spans("run `cargo test` now"), vec![Text("run "), Code("cargo test"), Text(" now")]);
+    assert_eq!(markdown_lite_spans("see [the docs](https://docs.rs)"), vec![Text("see "), Link("the docs", "https://docs.rs")]);
+    assert_eq!(markdown_lite_spans("**ñ**`ü`"), vec![Bold("ñ"), Code("ü")]);
+}
+
+#[test]
+fn markdown_lite_unterminated() {
+    use MarkdownSpan::*;
+    assert_eq!(markdown_lite_spans("a **bold"), vec![Text("a **bold")]);
+    assert_eq!(markdown_lite_spans("a `code"), vec![Text("a `code")]);
+    assert_eq!(markdown_lite_spans("[text](url"), vec![Text("[text](url")]);
+    assert_eq!(markdown_lite_spans("[text] (url)"), vec![Text("[text] (url)")]);
+}
+
+#[test]
+fn markdown_lite_nested_brackets() {
+    use MarkdownSpan::*;
+    assert_eq!(markdown_lite_spans("[a [b] c](url)"), vec![Link("a [b] c", "url")]);
+    assert_eq!(markdown_lite_spans("[x] [y](url)"), vec![Text("[x] "), Link("y", "url")]);
+    assert_eq!(markdown_lite_spans("[[x](url)"), vec![Text("["), Link("x", "url")]);
+}
+
+#[test]
+fn scrolling_plot_wraparound() {
+    let mut plot = ScrollingPlot::new(3);
+    assert!(plot.is_empty());
+    plot.push(1.0);
+    plot.push(2.0);
+    assert_eq!(plot.iter().collect::<Vec<_>>(), [1.0, 2.0]);
+    plot.push(3.0);
+    assert_eq!(plot.iter().collect::<Vec<_>>(), [1.0, 2.0, 3.0]);
+    // Full: each push drops the oldest value, and the order is kept across the wraparound
+    for (v, expected) in [(4.0, [2.0, 3.0, 4.0]), (5.0, [3.0, 4.0, 5.0]), (6.0, [4.0, 5.0, 6.0]), (7.0, [5.0, 6.0, 7.0])] {
+        plot.push(v);
+        assert_eq!(plot.len(), 3);
+        assert_eq!(plot.iter().collect::<Vec<_>>(), expected);
+    }
+    plot.clear();
+    assert!(plot.is_empty());
+    plot.push(8.0);
+    assert_eq!(plot.iter().collect::<Vec<_>>(), [8.0]);
+    assert_eq!(plot.capacity(), 3);
+}
+
+#[test]
+fn mouse_button_round_trip() {
+    for button in [MouseButton::Left, MouseButton::Right, MouseButton::Middle, MouseButton::Other(3), MouseButton::Other(4)] {
+        assert_eq!(MouseButton::try_from(i32::from(button)), Ok(button));
+    }
+    assert_eq!(MouseButton::try_from(0), Ok(MouseButton::Left));
+    assert_eq!(MouseButton::try_from(2), Ok(MouseButton::Middle));
+    let count = ImGuiMouseButton_::ImGuiMouseButton_COUNT.0 as i32;
+    assert_eq!(MouseButton::try_from(count), Err(count));
+    assert_eq!(MouseButton::try_from(-1), Err(-1));
+    assert_eq!(MouseButton::try_from(i32::MAX), Err(i32::MAX));
+}
+
+struct SortApp {
+    header: Vector2,
+    names: Vec<Option<String>>,
+    sorted: Vec<(ImGuiID, SortDirection)>,
+}
+
+impl UiBuilder for SortApp {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        test_window(ui, || {
+            ui.table_config("table", 2).flags(TableFlags::Sortable).with(|| {
+                ui.table_setup_column("first", TableColumnFlags::None, 0.0, 10);
+                ui.table_setup_column("second", TableColumnFlags::None, 0.0, 20);
+                ui.table_next_row(TableRowFlags::Headers, 0.0);
+                for column in 0..2 {
+                    ui.table_set_column_index(column);
+                    ui.table_header(ui.table_get_column_name(None).unwrap());
+                    if column == 1 {
+                        self.header = (ui.get_item_rect_min() + ui.get_item_rect_max()) / 2.0;
+                    }
+                }
+                self.names = [Some(0), Some(1), Some(2), Some(-1)]
+                    .into_iter()
+                    .map(|n| ui.table_get_column_name(n))
+                    .collect();
+                if let Some(mut specs) = ui.table_get_sort_specs() {
+                    if specs.is_dirty() {
+                        self.sorted = specs.iter().map(|spec| (spec.id(), spec.sort_direction())).collect();
+                        specs.set_clean();
+                    }
+                }
+            });
+            // Outside of a table there are no columns
+            self.names.push(ui.table_get_column_name(None));
+        });
+    }
+}
+
+#[test]
+fn table_sort_specs_header_click() {
+    let mut app = SortApp { header: vec2(0.0, 0.0), names: Vec::new(), sorted: Vec::new() };
+    let mut tc = TestContext::new();
+    tc.frame(&mut app, |_| {});
+    assert_eq!(app.names, [Some("first".to_owned()), Some("second".to_owned()), None, None, None]);
+    // The first sortable column is the default
+    assert_eq!(app.sorted, [(10, SortDirection::Ascending)]);
+
+    let header = app.header;
+    tc.click(&mut app, header);
+    assert_eq!(app.sorted, [(20, SortDirection::Ascending)]);
+}
+
+struct EndTooManyApp;
+
+impl UiBuilder for EndTooManyApp {
+    fn do_ui(&mut self, _ui: &Ui<Self>) {
+        // Only the implicit "Debug" window is open, Dear ImGui asserts and ignores this call
+        unsafe {
+            ImGui_End();
+        }
+    }
+}
+
+#[test]
+fn error_callback_recovers() {
+    let errors = Rc::new(RefCell::new(Vec::new()));
+    let mut tc = TestContext::new();
+    let log = Rc::clone(&errors);
+    unsafe {
+        tc.ctx.set_error_callback(move |msg| log.borrow_mut().push(msg.to_owned()));
+    }
+    tc.frame(&mut EndTooManyApp, |_| {});
+    assert_eq!(errors.borrow().len(), 1);
+    assert!(errors.borrow()[0].contains("End()"), "{}", errors.borrow()[0]);
+    // The context is still usable
+    tc.frame(&mut EndTooManyApp, |_| {});
+    assert_eq!(errors.borrow().len(), 2);
+}
+
+struct PanicApp {
+    panic: bool,
+    done: bool,
+}
+
+impl UiBuilder for PanicApp {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        test_window(ui, || {
+            ui.child_config("child").with(|| {
+                // A window that is never closed, Dear ImGui has to recover from it
+                unsafe {
+                    ImGui_Begin(b"unbalanced\0".as_ptr() as *const c_char, null_mut(), 0);
+                }
+                if self.panic {
+                    panic!("panic in do_ui");
+                }
+                unsafe {
+                    ImGui_End();
+                }
+            });
+        });
+        self.done = true;
+    }
+}
+
+#[test]
+fn panic_in_frame_recovers() {
+    let mut tc = TestContext::new();
+    let mut app = PanicApp { panic: true, done: false };
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        tc.frame(&mut app, |_| {});
+    }));
+    assert!(res.is_err());
+    assert!(!app.done);
+    unsafe {
+        assert!(!(*tc.ctx.imgui).WithinFrameScope);
+        assert!((*ImGui_GetIO()).BackendLanguageUserData.is_null());
+    }
+
+    app.panic = false;
+    tc.frame(&mut app, |_| {});
+    assert!(app.done);
+}
+
+struct CallbackApp {
+    id: Option<usize>,
+    calls: Rc<Cell<u32>>,
+}
+
+impl UiBuilder for CallbackApp {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        unsafe {
+            // The callback from the previous frame is gone, this must do nothing
+            if let Some(id) = self.id {
+                Ui::<Self>::run_callback(id, ());
+            }
+            let calls = Rc::clone(&self.calls);
+            let id = ui.push_callback(move |_, ()| calls.set(calls.get() + 1));
+            Ui::<Self>::run_callback(id, ());
+            self.id = Some(id);
+        }
+    }
+}
+
+#[test]
+fn stale_callback_is_ignored() {
+    let calls = Rc::new(Cell::new(0));
+    let mut app = CallbackApp { id: None, calls: Rc::clone(&calls) };
+    let mut tc = TestContext::new();
+    for frame in 1..=3 {
+        tc.frame(&mut app, |_| {});
+        assert_eq!(calls.get(), frame);
+    }
+    // And outside of a frame there is no `Ui` at all
+    unsafe {
+        Ui::<CallbackApp>::run_callback(app.id.unwrap(), ());
+    }
+    assert_eq!(calls.get(), 3);
+}
+
+struct EmptyApp;
+
+impl UiBuilder for EmptyApp {
+    fn do_ui(&mut self, _ui: &Ui<Self>) {}
+}
+
+#[test]
+fn scale_change_invalidates_atlas() {
+    let mut tc = TestContext::new();
+    let mut cur = tc.current();
+    unsafe {
+        assert!(cur.update_atlas(&mut EmptyApp));
+    }
+    // A resize, or setting the same scale again, keeps the atlas
+    cur.set_display_size(1024.0, 768.0);
+    assert!(!cur.ctx.pending_atlas);
+    cur.set_size(vec2(640.0, 480.0), 1.0);
+    assert!(!cur.ctx.pending_atlas);
+
+    cur.set_framebuffer_scale(2.0, 2.0);
+    assert!(cur.ctx.pending_atlas);
+    unsafe {
+        assert_eq!((*ImGui_GetIO()).FontGlobalScale, 0.5);
+        assert!(cur.update_atlas(&mut EmptyApp));
+    }
+    cur.set_size(vec2(800.0, 600.0), 2.0);
+    assert!(!cur.ctx.pending_atlas);
+}
+
+#[test]
+fn dashed_line_segments() {
+    let p1 = vec2(0.0, 0.0);
+    let p2 = vec2(10.0, 0.0);
+    let xs = |dash, gap| dash_segments(p1, p2, dash, gap).iter().map(|(a, b)| (a.x, b.x)).collect::<Vec<_>>();
+    // The last dash is cut at the end of the line
+    assert_eq!(xs(2.0, 1.0), [(0.0, 2.0), (3.0, 5.0), (6.0, 8.0), (9.0, 10.0)]);
+    assert_eq!(xs(5.0, 5.0), [(0.0, 5.0)]);
+    // A dash as long as the line is the whole line
+    assert_eq!(xs(10.0, 1.0), [(0.0, 10.0)]);
+    assert_eq!(xs(f32::INFINITY, 1.0), [(0.0, 10.0)]);
+    // No gap is a solid line, in many segments
+    assert_eq!(xs(1.0, -5.0).len(), 10);
+    // Nothing to draw
+    for dash in [0.0, -1.0, f32::NAN] {
+        assert!(xs(dash, 1.0).is_empty());
+    }
+    assert!(dash_segments(p1, p1, 2.0, 1.0).is_empty());
+    assert_eq!(xs(2.0, f32::INFINITY), [(0.0, 2.0)]);
+    // Far from the origin a dash is smaller than the float precision, but they are still counted
+    assert_eq!(dash_segments(vec2(1.0e8, 0.0), vec2(1.0e8 + 64.0, 0.0), 1.0, 1.0).len(), 32);
+}
+
+struct SpacerApp {
+    start: Vector2,
+    button: Vector2,
+}
+
+impl UiBuilder for SpacerApp {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        test_window(ui, || {
+            self.start = ui.get_cursor_screen_pos();
+            ui.horizontal_spacer(50.0);
+            ui.button("button");
+            self.button = ui.get_item_rect_min();
+        });
+    }
+}
+
+#[test]
+fn horizontal_spacer_width() {
+    let mut app = SpacerApp { start: vec2(0.0, 0.0), button: vec2(0.0, 0.0) };
+    TestContext::new().frame(&mut app, |_| {});
+    assert_eq!(app.button, app.start + vec2(50.0, 0.0));
+}
+
+struct TextClipApp {
+    text_width: f32,
+    // The horizontal extent of the vertices of the clipped text
+    drawn: Option<(f32, f32)>,
+}
+
+const CLIP_MIN: Vector2 = vec2(100.0, 100.0);
+const CLIP_MAX: Vector2 = vec2(150.0, 120.0);
+
+impl UiBuilder for TextClipApp {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        let text = "a long text that does not fit in the rectangle";
+        test_window(ui, || unsafe {
+            self.text_width = ui.calc_text_size(text).x;
+            let dl = ImGui_GetWindowDrawList();
+            let first = (*dl).VtxBuffer.Size as usize;
+            ui.text_clipped(CLIP_MIN, CLIP_MAX, text, vec2(0.0, 0.0));
+            self.drawn = (*dl).VtxBuffer.iter().skip(first).fold(None, |acc, v| {
+                let (lo, hi) = acc.unwrap_or((v.pos.x, v.pos.x));
+                Some((lo.min(v.pos.x), hi.max(v.pos.x)))
+            });
+        });
+    }
+}
+
+#[test]
+fn text_clipped_is_clipped() {
+    let mut app = TextClipApp { text_width: 0.0, drawn: None };
+    TestContext::new().frame(&mut app, |_| {});
+    assert!(app.text_width > CLIP_MAX.x - CLIP_MIN.x);
+    let (lo, hi) = app.drawn.expect("no text drawn");
+    assert!(lo >= CLIP_MIN.x && hi <= CLIP_MAX.x, "{lo}..{hi}");
+}
+
+struct ButtonApp {
+    clicks: u32,
+}
+
+impl UiBuilder for ButtonApp {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        test_window(ui, || {
+            ui.set_cursor_screen_pos(vec2(100.0, 100.0));
+            if ui.button_config("button").size(vec2(50.0, 50.0)).build() {
+                self.clicks += 1;
+            }
+        });
+    }
+}
+
+#[test]
+fn touch_tap_clicks() {
+    let mut app = ButtonApp { clicks: 0 };
+    let mut tc = TestContext::new();
+    let pos = vec2(120.0, 120.0);
+    // A first frame, so that the window is there to be touched
+    tc.frame(&mut app, |_| {});
+
+    tc.frame(&mut app, |cur| assert!(cur.add_touch_event(1, pos, true)));
+    unsafe {
+        assert_eq!((*ImGui_GetIO()).MouseSource, ImGuiMouseSource::ImGuiMouseSource_TouchScreen);
+    }
+    // A second finger is not the mouse
+    tc.frame(&mut app, |cur| assert!(!cur.add_touch_event(2, vec2(300.0, 300.0), true)));
+    assert_eq!(app.clicks, 0);
+    tc.frame(&mut app, |cur| assert!(cur.add_touch_event(1, pos, false)));
+    assert_eq!(app.clicks, 1);
+
+    // A cancelled touch does not click
+    tc.frame(&mut app, |cur| assert!(cur.add_touch_event(3, pos, true)));
+    tc.frame(&mut app, |cur| cur.add_touch_cancel_event(3));
+    tc.frame(&mut app, |_| {});
+    assert_eq!(app.clicks, 1);
+}
+
+struct SplitterApp {
+    sizes: (f32, f32),
+}
+
+impl UiBuilder for SplitterApp {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        test_window(ui, || {
+            ui.set_cursor_screen_pos(vec2(100.0, 100.0));
+            let (size1, size2) = &mut self.sizes;
+            ui.splitter("split", true, 8.0, size1, size2, 50.0, 50.0);
+        });
+    }
+}
+
+#[test]
+fn splitter_drag() {
+    let mut app = SplitterApp { sizes: (200.0, 300.0) };
+    let mut tc = TestContext::new();
+    tc.frame(&mut app, |_| {});
+    tc.mouse_frame(&mut app, vec2(104.0, 150.0), false);
+    tc.mouse_frame(&mut app, vec2(104.0, 150.0), true);
+    assert_eq!(app.sizes, (200.0, 300.0));
+
+    tc.mouse_frame(&mut app, vec2(134.0, 150.0), true);
+    assert_eq!(app.sizes, (230.0, 270.0));
+    // Too far to the left, `size1` stops at its minimum and the sum is kept
+    tc.mouse_frame(&mut app, vec2(-366.0, 150.0), true);
+    assert_eq!(app.sizes, (50.0, 450.0));
+    tc.mouse_frame(&mut app, vec2(1000.0, 150.0), true);
+    assert_eq!(app.sizes, (450.0, 50.0));
+
+    // Released, it does nothing
+    tc.mouse_frame(&mut app, vec2(1000.0, 150.0), false);
+    tc.mouse_frame(&mut app, vec2(500.0, 150.0), false);
+    assert_eq!(app.sizes, (450.0, 50.0));
+}
+
+fn snapshot_counts(snapshot: &DrawDataSnapshot) -> Vec<(usize, usize, usize)> {
+    snapshot.lists
+        .iter()
+        .map(|list| (list.vertices.len(), list.indices.len(), list.commands.len()))
+        .collect()
+}
+
+fn snapshot_positions(snapshot: &DrawDataSnapshot) -> Vec<Vector2> {
+    snapshot.lists
+        .iter()
+        .flat_map(|list| list.vertices.iter().map(|v| im_to_v2(v.pos)))
+        .collect()
+}
+
+#[test]
+fn draw_data_snapshot_is_owned() {
+    let mut app = ButtonApp { clicks: 0 };
+    let mut tc = TestContext::new();
+    tc.frame(&mut app, |_| {});
+    tc.frame(&mut app, |_| {});
+    let first = tc.current().snapshot_draw_data().unwrap();
+    let counts = snapshot_counts(&first);
+    let positions = snapshot_positions(&first);
+    assert!(!positions.is_empty());
+
+    // The same UI draws the same
+    tc.frame(&mut app, |_| {});
+    let second = tc.current().snapshot_draw_data().unwrap();
+    assert_eq!(snapshot_counts(&second), counts);
+    assert_eq!(snapshot_positions(&second), positions);
+
+    // A different UI does not change the old snapshots
+    tc.frame(&mut EmptyApp, |_| {});
+    let third = tc.current().snapshot_draw_data().unwrap();
+    assert_ne!(snapshot_counts(&third), counts);
+    assert_eq!(snapshot_counts(&first), counts);
+    assert_eq!(snapshot_positions(&first), positions);
+}
+
+struct DragRangeApp {
+    range: (i32, i32),
+    start: Vector2,
+    // The rectangle of the max widget, the last item
+    max_rect: (Vector2, Vector2),
+}
+
+impl UiBuilder for DragRangeApp {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        test_window(ui, || {
+            self.start = ui.get_cursor_screen_pos();
+            let (current_min, current_max) = &mut self.range;
+            // No label, so the max widget is the last item
+            ui.drag_int_range_2_config("##range", current_min, current_max).build();
+            self.max_rect = (ui.get_item_rect_min(), ui.get_item_rect_max());
+        });
+    }
+}
+
+// Drags the mouse with the left button from `from` to `to`.
+fn drag<A: UiBuilder>(tc: &mut TestContext, app: &mut A, from: Vector2, to: Vector2) {
+    tc.mouse_frame(app, from, false);
+    tc.mouse_frame(app, from, true);
+    tc.mouse_frame(app, to, true);
+    tc.mouse_frame(app, to, false);
+}
+
+#[test]
+fn drag_int_range_keeps_order() {
+    let mut app = DragRangeApp { range: (10, 20), start: vec2(0.0, 0.0), max_rect: (vec2(0.0, 0.0), vec2(0.0, 0.0)) };
+    let mut tc = TestContext::new();
+    tc.frame(&mut app, |_| {});
+    let (max_min, max_max) = app.max_rect;
+    let max_center = (max_min + max_max) / 2.0;
+    // Both widgets have the same size
+    let min_center = app.start + (max_max - max_min) / 2.0;
+
+    // The max dragged below the min stops at the min
+    drag(&mut tc, &mut app, max_center, max_center - vec2(300.0, 0.0));
+    assert_eq!(app.range, (10, 10));
+
+    // And the min dragged above the max stops at the max
+    app.range = (10, 20);
+    drag(&mut tc, &mut app, min_center, min_center + vec2(300.0, 0.0));
+    assert_eq!(app.range, (20, 20));
+}
+
+fn data_type_size<T: DataType>() -> usize {
+    // Warning! internal imgui API ahead
+    unsafe { (*ImGui_DataTypeGetInfo(T::KIND)).Size }
+}
+
+#[test]
+fn data_type_kind() {
+    assert_eq!(<u64 as DataType>::KIND, ImGuiDataType_::ImGuiDataType_U64.0 as ImGuiDataType);
+    assert_eq!(<f32 as DataType>::KIND, ImGuiDataType_::ImGuiDataType_Float.0 as ImGuiDataType);
+    // Dear ImGui reads and writes as many bytes as the Rust type has
+    assert_eq!(data_type_size::<i8>(), 1);
+    assert_eq!(data_type_size::<u8>(), 1);
+    assert_eq!(data_type_size::<i16>(), 2);
+    assert_eq!(data_type_size::<u16>(), 2);
+    assert_eq!(data_type_size::<i32>(), 4);
+    assert_eq!(data_type_size::<u32>(), 4);
+    assert_eq!(data_type_size::<i64>(), 8);
+    assert_eq!(data_type_size::<u64>(), 8);
+    assert_eq!(data_type_size::<f32>(), 4);
+    assert_eq!(data_type_size::<f64>(), 8);
+}
+
+struct InputU64App {
+    value: u64,
+    rect: (Vector2, Vector2),
+}
+
+impl UiBuilder for InputU64App {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        test_window(ui, || {
+            ui.input_scalar_config("##value", &mut self.value).build();
+            self.rect = (ui.get_item_rect_min(), ui.get_item_rect_max());
+        });
+    }
+}
+
+#[test]
+fn input_scalar_u64_full_range() {
+    let mut app = InputU64App { value: 0, rect: (vec2(0.0, 0.0), vec2(0.0, 0.0)) };
+    let mut tc = TestContext::new();
+    tc.frame(&mut app, |_| {});
+    let (min, max) = app.rect;
+    // The click selects all the text, so typing replaces it
+    tc.click(&mut app, (min + max) / 2.0);
+    let text = u64::MAX.to_string();
+    tc.frame(&mut app, |_| unsafe {
+        for c in text.chars() {
+            ImGuiIO_AddInputCharacter(ImGui_GetIO(), c as u32);
+        }
+    });
+    assert_eq!(app.value, u64::MAX);
+}
+
+// Any non-null pointer will do, nothing is actually drawn
+const FAKE_TEXTURE: usize = 0x1234;
+
+struct TextureApp;
+
+impl UiBuilder for TextureApp {
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        test_window(ui, || {
+            let dl = ui.window_draw_list();
+            let texture = unsafe { TextureId::from_id(FAKE_TEXTURE as ImTextureID) };
+            dl.with_texture(texture, || {
+                let white = Color::new(1.0, 1.0, 1.0, 1.0);
+                dl.prim_rect_uv(vec2(10.0, 10.0), vec2(20.0, 20.0), vec2(0.0, 0.0), vec2(1.0, 1.0), white);
+                dl.prim_rect_uv(vec2(30.0, 10.0), vec2(40.0, 20.0), vec2(0.0, 0.0), vec2(1.0, 1.0), white);
+            });
+            ui.text("after");
+        });
+    }
+}
+
+#[test]
+fn with_texture_single_command() {
+    let mut tc = TestContext::new();
+    tc.frame(&mut TextureApp, |_| {});
+    let snapshot = tc.current().snapshot_draw_data().unwrap();
+    let texture = unsafe { TextureId::from_id(FAKE_TEXTURE as ImTextureID) };
+    let commands: Vec<_> = snapshot.lists
+        .iter()
+        .flat_map(|list| &list.commands)
+        .filter(|cmd| cmd.elem_count > 0)
+        .collect();
+    let textured: Vec<_> = commands.iter().filter(|cmd| cmd.texture_id == texture).collect();
+    // Two rectangles, 6 indices each, in one command
+    assert_eq!(textured.len(), 1);
+    assert_eq!(textured[0].elem_count, 12);
+    // The texture is popped, the text uses the font texture
+    assert!(commands.iter().any(|cmd| cmd.texture_id != texture));
+}
+
+struct FontApp {
+    snap: bool,
+}
+
+impl UiBuilder for FontApp {
+    fn build_custom_atlas(&mut self, atlas: &mut FontAtlasMut<'_, Self>) {
+        atlas.add_font(FontInfo::default_font(13.0).snap_size_to_pixels(self.snap));
+    }
+    fn do_ui(&mut self, _ui: &Ui<Self>) {}
+}
+
+fn baked_font_size(snap: bool) -> f32 {
+    let mut tc = TestContext::with_scale(1.5);
+    let mut cur = tc.current();
+    unsafe {
+        cur.update_atlas(&mut FontApp { snap });
+        let fonts = &(*(*ImGui_GetIO()).Fonts).Fonts;
+        (*fonts[0]).FontSize
+    }
+}
+
+#[test]
+fn font_size_snapped_to_pixels() {
+    // 13 * 1.5 = 19.5
+    assert_eq!(baked_font_size(true), 20.0);
+    assert_eq!(baked_font_size(false), 19.5);
+}